@@ -2,7 +2,7 @@ use stats_alloc::{INSTRUMENTED_SYSTEM, Region, StatsAlloc};
 use std::alloc::System;
 use std::collections::LinkedList as StdLinkedList;
 use byte_unit::Byte;
-use xor_ll::LinkedList as XorLinkedList;
+use xor_ll::{ArenaXorList, LinkedList as XorLinkedList};
 
 #[global_allocator]
 static GLOBAL: &StatsAlloc<System> = &INSTRUMENTED_SYSTEM;
@@ -12,7 +12,7 @@ fn main() {
         println!("\n Iters: {n}");
         // Benchmark XorLinkedList
         {
-            let reg = Region::new(&GLOBAL);
+            let reg = Region::new(GLOBAL);
             let mut xor_list = XorLinkedList::new();
             for i in 0..n {
                 xor_list.push_back(i);
@@ -22,9 +22,21 @@ fn main() {
             println!("XorLinkedList: {}", Byte::from_u64(xor_stats.bytes_allocated as u64).get_appropriate_unit(byte_unit::UnitType::Binary));
         }
 
+        // Benchmark ArenaXorList (slab reserved up front, so growth reallocations are not counted)
+        {
+            let reg = Region::new(GLOBAL);
+            let mut arena_list = ArenaXorList::with_capacity(n);
+            for i in 0..n {
+                arena_list.push_back(i);
+            }
+            drop(arena_list);
+            let arena_stats = reg.change();
+            println!("ArenaXorList: {}", Byte::from_u64(arena_stats.bytes_allocated as u64).get_appropriate_unit(byte_unit::UnitType::Binary));
+        }
+
         // Benchmark StdLinkedList
         {
-            let reg = Region::new(&GLOBAL);
+            let reg = Region::new(GLOBAL);
             let mut std_list = StdLinkedList::new();
             for i in 0..n {
                 std_list.push_back(i);
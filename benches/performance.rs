@@ -9,7 +9,7 @@ fn bench_push_front(c: &mut Criterion) {
     for &n in &[1_000u32, 10_000, 100_000, 1_000_000] {
         group.bench_with_input(format!("xor_front_{}", n), &n, |b, &n| {
             b.iter_batched(
-                || XorLinkedList::new(),
+                XorLinkedList::new,
                 |mut list| {
                     for i in 0..n {
                         list.push_front(black_box(i));
@@ -22,7 +22,7 @@ fn bench_push_front(c: &mut Criterion) {
 
         group.bench_with_input(format!("std_front_{}", n), &n, |b, &n| {
             b.iter_batched(
-                || StdLinkedList::new(),
+                StdLinkedList::new,
                 |mut list| {
                     for i in 0..n {
                         list.push_front(black_box(i));
@@ -43,7 +43,7 @@ fn bench_push_back(c: &mut Criterion) {
     for &n in &[1_000u32, 10_000, 100_000] {
         group.bench_with_input(format!("xor_back_{}", n), &n, |b, &n| {
             b.iter_batched(
-                || XorLinkedList::new(),
+                XorLinkedList::new,
                 |mut list| {
                     for i in 0..n {
                         list.push_back(black_box(i));
@@ -56,7 +56,7 @@ fn bench_push_back(c: &mut Criterion) {
 
         group.bench_with_input(format!("std_back_{}", n), &n, |b, &n| {
             b.iter_batched(
-                || StdLinkedList::new(),
+                StdLinkedList::new,
                 |mut list| {
                     for i in 0..n {
                         list.push_back(black_box(i));
@@ -155,12 +155,55 @@ fn bench_pop_back(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_queue(c: &mut Criterion) {
+    let mut group = c.benchmark_group("queue");
+
+    for &n in &[1_000u32, 10_000, 100_000] {
+        group.bench_with_input(format!("xor_queue_{}", n), &n, |b, &n| {
+            let mut list = XorLinkedList::new();
+            list.extend(0..64);
+            b.iter(|| {
+                for i in 0..n {
+                    black_box(list.pop_front());
+                    list.push_back(black_box(i));
+                }
+            });
+        });
+
+        group.bench_with_input(format!("xor_cached_queue_{}", n), &n, |b, &n| {
+            let mut list = XorLinkedList::new();
+            list.set_node_cache_limit(16);
+            list.extend(0..64);
+            b.iter(|| {
+                for i in 0..n {
+                    black_box(list.pop_front());
+                    list.push_back(black_box(i));
+                }
+            });
+        });
+
+        group.bench_with_input(format!("std_queue_{}", n), &n, |b, &n| {
+            let mut list = StdLinkedList::new();
+            list.extend(0..64);
+            b.iter(|| {
+                for i in 0..n {
+                    black_box(list.pop_front());
+                    list.push_back(black_box(i));
+                }
+            });
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_push_front,
     bench_push_back,
     bench_pop_front,
-    bench_pop_back
+    bench_pop_back,
+    bench_queue
 );
 criterion_main! {
     benches
@@ -0,0 +1,363 @@
+use alloc::alloc::{alloc, dealloc};
+use core::alloc::Layout;
+use core::ptr::NonNull;
+
+use crate::AllocError;
+
+/// Provides memory for the nodes of a [`LinkedList`](crate::LinkedList).
+///
+/// This is a minimal, stable stand-in for the unstable `std::alloc::Allocator`. Implement it
+/// for a bump arena or a pool to keep a list's nodes out of the global allocator.
+///
+/// # Safety
+///
+/// A block returned by `allocate` must stay valid and must not be handed out again until it is
+/// passed to `deallocate`, either of this allocator or of any value cloned from it. Clones must
+/// behave like the same allocator - lists made by [`split_off`](crate::LinkedList::split_off)
+/// carry a clone and free the nodes they took over through it.
+///
+/// Moving nodes between two existing lists ([`append`](crate::LinkedList::append),
+/// `merge`, `splice_*`) requires their allocators to be equal. So if the type implements
+/// `PartialEq`, equal values must be able to free each other's blocks as well. Comparing
+/// references, `&A`, compares the allocators they point to, which keeps to the same rule.
+pub unsafe trait NodeAllocator {
+    /// Allocates a block fitting `layout`, which is never zero-sized.
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError>;
+
+    /// Deallocates a block previously returned by `allocate` with the same `layout`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must denote a block currently allocated by this allocator (or a clone of it)
+    /// with the given `layout`.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+}
+
+/// The global memory allocator, used by lists created with [`LinkedList::new`](crate::LinkedList::new).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Global;
+
+unsafe impl NodeAllocator for Global {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        debug_assert!(layout.size() != 0);
+        NonNull::new(unsafe { alloc(layout) }).ok_or(AllocError)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { dealloc(ptr.as_ptr(), layout) }
+    }
+}
+
+unsafe impl<A: NodeAllocator + ?Sized> NodeAllocator for &A {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        (**self).allocate(layout)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { (**self).deallocate(ptr, layout) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, UnsafeCell};
+    use std::mem::MaybeUninit;
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+    use std::prelude::rust_2024::*;
+
+    use super::*;
+    use crate::{IndexOutOfBounds, InsertError, LinkedList, Node};
+
+    /// Forwards to `Global` and counts live allocations.
+    #[derive(Default)]
+    struct Counting {
+        allocated: Cell<usize>,
+        deallocated: Cell<usize>,
+    }
+
+    impl Counting {
+        fn live(&self) -> usize {
+            self.allocated.get() - self.deallocated.get()
+        }
+    }
+
+    // every instance counts on its own, so only the very same one is equal
+    impl PartialEq for Counting {
+        fn eq(&self, other: &Self) -> bool {
+            std::ptr::eq(self, other)
+        }
+    }
+
+    unsafe impl NodeAllocator for Counting {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+            self.allocated.set(self.allocated.get() + 1);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.deallocated.set(self.deallocated.get() + 1);
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+
+    /// Forwards to `Global` until `remaining` allocations have been made, then fails.
+    struct Limited {
+        remaining: Cell<usize>,
+    }
+
+    unsafe impl NodeAllocator for Limited {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+            match self.remaining.get() {
+                0 => Err(AllocError),
+                n => {
+                    self.remaining.set(n - 1);
+                    Global.allocate(layout)
+                }
+            }
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+
+    /// Hands out memory from a fixed buffer and never reuses it.
+    struct Bump {
+        buffer: UnsafeCell<[MaybeUninit<u8>; 4096]>,
+        offset: Cell<usize>,
+    }
+
+    impl Bump {
+        fn new() -> Self {
+            Bump {
+                buffer: UnsafeCell::new([MaybeUninit::uninit(); 4096]),
+                offset: Cell::new(0),
+            }
+        }
+
+        fn contains<T>(&self, ptr: *const T) -> bool {
+            let start = self.buffer.get() as usize;
+            (start..start + 4096).contains(&(ptr as usize))
+        }
+    }
+
+    unsafe impl NodeAllocator for Bump {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+            let start = self.buffer.get() as usize;
+            let aligned = (start + self.offset.get()).next_multiple_of(layout.align());
+            let end = aligned + layout.size();
+            if end > start + 4096 {
+                return Err(AllocError);
+            }
+            self.offset.set(end - start);
+            NonNull::new(aligned as *mut u8).ok_or(AllocError)
+        }
+
+        unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {}
+    }
+
+    #[test]
+    fn every_node_goes_through_the_allocator() {
+        let alloc = Counting::default();
+        {
+            let mut list = LinkedList::new_in(&alloc);
+            list.push_back(1);
+            list.push_back(2);
+            list.push_front(0);
+            list.insert(1, 10);
+            assert_eq!(alloc.allocated.get(), 4);
+
+            assert_eq!(list.pop_back(), Some(2));
+            assert_eq!(list.remove(1), 10);
+            assert_eq!(alloc.live(), 2);
+
+            list.extend([3, 4, 5]);
+            list.retain(|x| x % 2 == 1);
+            assert_eq!(alloc.live(), list.len());
+        }
+        assert_eq!(alloc.live(), 0);
+    }
+
+    #[test]
+    fn clear_and_into_iter_deallocate() {
+        let alloc = Counting::default();
+        let mut list = LinkedList::new_in(&alloc);
+        list.extend(0..10);
+        list.clear();
+        assert_eq!(alloc.live(), 0);
+
+        list.extend(0..10);
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(alloc.live(), 9);
+        drop(iter);
+        assert_eq!(alloc.live(), 0);
+    }
+
+    #[test]
+    fn split_off_and_append_share_the_allocator() {
+        let alloc = Counting::default();
+        let mut list = LinkedList::new_in(&alloc);
+        list.extend(0..6);
+
+        let mut tail = list.split_off(3);
+        assert!(std::ptr::eq(*tail.allocator(), &alloc));
+        let mut cursor = tail.cursor_front_mut();
+        let single = cursor.remove_current_as_list().unwrap();
+        assert_eq!(single.len(), 1);
+        drop(single);
+        assert_eq!(alloc.live(), 5);
+
+        list.append(&mut tail);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 4, 5]
+        );
+        let cloned = list.clone();
+        assert_eq!(alloc.live(), 10);
+        drop(cloned);
+        drop(list);
+        drop(tail);
+        assert_eq!(alloc.live(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "another allocator")]
+    fn append_with_another_allocator_panics() {
+        let (a, b) = (Counting::default(), Counting::default());
+        let mut list = LinkedList::new_in(&a);
+        list.extend(0..3);
+        let mut other = LinkedList::new_in(&b);
+        other.extend(3..6);
+        list.append(&mut other);
+    }
+
+    #[test]
+    fn merge_and_splice_check_the_allocator() {
+        let (a, b) = (Counting::default(), Counting::default());
+        let mut list = LinkedList::new_in(&a);
+        list.extend([1, 3]);
+        let mut other = LinkedList::new_in(&b);
+        other.extend([2]);
+        let merged = catch_unwind(AssertUnwindSafe(|| list.merge(&mut other)));
+        assert!(merged.is_err());
+        let spliced = catch_unwind(AssertUnwindSafe(|| {
+            list.cursor_front_mut().splice_after(other.split_off(0))
+        }));
+        assert!(spliced.is_err());
+        // nothing was moved, so every node is still freed by its own allocator
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 3]);
+        drop(list);
+        drop(other);
+        assert_eq!((a.live(), b.live()), (0, 0));
+    }
+
+    #[test]
+    fn list_in_a_bump_arena() {
+        let arena = Bump::new();
+        let mut list = LinkedList::new_in(&arena);
+        list.extend(0..50u64);
+
+        assert!(list.iter().all(|x| arena.contains(x)));
+        assert_eq!(list.iter().sum::<u64>(), (0..50).sum::<u64>());
+        assert_eq!(list.back(), Some(&49));
+    }
+
+    #[test]
+    fn node_cache_recycles_nodes() {
+        let alloc = Counting::default();
+        let mut list = LinkedList::new_in(&alloc);
+        list.set_node_cache_limit(4);
+        list.extend(0..4);
+        assert_eq!(alloc.allocated.get(), 4);
+
+        for i in 4..1000 {
+            assert_eq!(list.pop_front(), Some(i - 4));
+            list.push_back(i);
+        }
+        assert_eq!(alloc.allocated.get(), 4);
+        assert_eq!(list.cached_nodes(), 0);
+
+        list.clear();
+        assert_eq!(list.cached_nodes(), 4);
+        assert_eq!(alloc.live(), 4);
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_after(1);
+        cursor.insert_after(0);
+        assert_eq!(cursor.remove_current(), None);
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(0));
+        assert_eq!(alloc.allocated.get(), 4);
+        assert_eq!(list.cached_nodes(), 3);
+
+        // only `limit` nodes are kept, the rest goes back to the allocator
+        list.extend(0..10);
+        list.clear();
+        assert_eq!(list.cached_nodes(), 4);
+        assert_eq!(alloc.live(), 4);
+
+        list.set_node_cache_limit(1);
+        assert_eq!(alloc.live(), 1);
+        list.release_cached_nodes();
+        assert_eq!(alloc.live(), 0);
+        assert_eq!(list.node_cache_limit(), 1);
+    }
+
+    #[test]
+    fn node_cache_is_released_on_drop() {
+        let alloc = Counting::default();
+        {
+            let mut list = LinkedList::new_in(&alloc);
+            list.set_node_cache_limit(usize::MAX);
+            list.extend((0..10).map(|i| i.to_string()));
+            assert_eq!(list.pop_back().as_deref(), Some("9"));
+
+            // the whole list moves out, the cached node stays
+            let all = list.split_off(0);
+            assert_eq!(all.len(), 9);
+            assert_eq!(list.cached_nodes(), 1);
+            drop(all);
+            assert_eq!(alloc.live(), 1);
+
+            list.push_back("a".to_string());
+            assert_eq!(list.cached_nodes(), 0);
+            assert_eq!(list.front().map(String::as_str), Some("a"));
+            list.pop_front();
+        }
+        assert_eq!(alloc.live(), 0);
+    }
+
+    #[test]
+    fn try_methods_report_allocation_failure() {
+        let alloc = Limited {
+            remaining: Cell::new(3),
+        };
+        let mut list = LinkedList::new_in(&alloc);
+        list.set_node_cache_limit(1);
+        list.extend([1, 2, 3]);
+
+        let err = list.try_push_back(4).unwrap_err();
+        assert_eq!(err.layout(), Layout::new::<Node<i32>>());
+        assert_eq!(list.try_push_front(0), Err(err));
+        assert_eq!(list.try_insert(1, 0), Err(InsertError::Alloc(err)));
+        assert_eq!(
+            list.try_insert(5, 0),
+            Err(InsertError::OutOfBounds(IndexOutOfBounds {
+                index: 5,
+                len: 3
+            }))
+        );
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        // a cached node needs no allocation
+        assert_eq!(list.remove(1), 2);
+        assert_eq!(list.try_insert(1, 5), Ok(()));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 5, 3]);
+        assert!(list.try_push_back(4).is_err());
+    }
+}
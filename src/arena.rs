@@ -0,0 +1,691 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
+
+use alloc::vec::Vec;
+
+/// Slot index, 1-based so that 0 can stand for "no slot" just like a null pointer does
+/// in [`LinkedList`](crate::LinkedList).
+type Index = u32;
+
+const NIL: Index = 0;
+
+struct Slot<T> {
+    value: MaybeUninit<T>,
+    // occupied slot: XOR of the prev and next indices
+    // vacant slot: index of the next vacant slot
+    xored: Index,
+}
+
+/// A XOR linked list whose nodes live in a single contiguous slab.
+///
+/// Links are `u32` XORs of slot indices instead of pointer sized XORs of addresses, and there is
+/// no per-node allocation. The slab may be moved or reallocated freely, the indices stay valid.
+/// Slots of removed elements are kept on a free chain and reused by later pushes.
+///
+/// Holds at most `u32::MAX` elements.
+pub struct ArenaXorList<T> {
+    slots: Vec<Slot<T>>,
+    begin: Index,
+    end: Index,
+    // head of the chain of vacant slots
+    free: Index,
+
+    len: usize,
+}
+
+impl<T> Default for ArenaXorList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ArenaXorList<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates an empty list with room for `capacity` elements before the slab grows.
+    pub fn with_capacity(capacity: usize) -> Self {
+        ArenaXorList {
+            slots: Vec::with_capacity(capacity),
+            begin: NIL,
+            end: NIL,
+            free: NIL,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of elements the slab can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.begin == NIL
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        let _ = self.push_back_mut(value);
+    }
+
+    pub fn push_back_mut(&mut self, value: T) -> &mut T {
+        let index = self.alloc_slot(value);
+        if self.end == NIL {
+            self.begin = index;
+        } else {
+            self.slot_mut(index).xored = self.end;
+            self.slot_mut(self.end).xored ^= index;
+        }
+        self.end = index;
+        self.len += 1;
+        unsafe { self.slot_mut(index).value.assume_init_mut() }
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        let _ = self.push_front_mut(value);
+    }
+
+    pub fn push_front_mut(&mut self, value: T) -> &mut T {
+        let index = self.alloc_slot(value);
+        if self.begin == NIL {
+            self.end = index;
+        } else {
+            self.slot_mut(index).xored = self.begin;
+            self.slot_mut(self.begin).xored ^= index;
+        }
+        self.begin = index;
+        self.len += 1;
+        unsafe { self.slot_mut(index).value.assume_init_mut() }
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let begin = self.begin;
+        if begin == NIL {
+            return None;
+        }
+
+        // `begin` has no predecessor, so its link is the next index
+        let next = self.slot(begin).xored;
+        if next == NIL {
+            self.end = NIL;
+        } else {
+            self.slot_mut(next).xored ^= begin;
+        }
+        self.begin = next;
+        self.len -= 1;
+
+        Some(self.free_slot(begin))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let end = self.end;
+        if end == NIL {
+            return None;
+        }
+
+        let prev = self.slot(end).xored;
+        if prev == NIL {
+            self.begin = NIL;
+        } else {
+            self.slot_mut(prev).xored ^= end;
+        }
+        self.end = prev;
+        self.len -= 1;
+
+        Some(self.free_slot(end))
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.value(self.begin)
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.value_mut(self.begin)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.value(self.end)
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.value_mut(self.end)
+    }
+
+    /// Drops all elements and releases the slab.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Reverses the order of the elements in O(1), see [`LinkedList::reverse`](crate::LinkedList::reverse).
+    pub fn reverse(&mut self) {
+        mem::swap(&mut self.begin, &mut self.end);
+    }
+
+    /// Splits the list into two at the given index. Returns everything after the given index,
+    /// including the index.
+    ///
+    /// Elements can't share a slab between two lists, so the shorter part is moved into a new
+    /// slab, which takes O(min(at, len - at)).
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "Cannot split off at a nonexistent index");
+
+        let mut other = Self::new();
+        if at <= self.len / 2 {
+            // move the head out and keep the tail in the current slab
+            other.slots.reserve(at);
+            for _ in 0..at {
+                other.push_back(self.pop_front().unwrap());
+            }
+            mem::swap(self, &mut other);
+        } else {
+            other.slots.reserve(self.len - at);
+            for _ in at..self.len {
+                other.push_front(self.pop_back().unwrap());
+            }
+        }
+        other
+    }
+
+    /// Moves all elements of `other` to the back of this list, leaving `other` empty.
+    ///
+    /// The shorter of the two lists is moved into the slab of the longer one,
+    /// which takes O(min(len, other.len)).
+    pub fn append(&mut self, other: &mut Self) {
+        if self.len < other.len {
+            while let Some(value) = self.pop_back() {
+                other.push_front(value);
+            }
+            mem::swap(self, other);
+        } else {
+            while let Some(value) = other.pop_front() {
+                self.push_back(value);
+            }
+        }
+    }
+
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq<T>,
+    {
+        self.iter().any(|e| e == x)
+    }
+
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            slots: &self.slots,
+            begin: self.begin,
+            end: self.end,
+            len: self.len,
+            begin_prev: NIL,
+            end_next: NIL,
+        }
+    }
+
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            slots: self.slots.as_mut_ptr(),
+            begin: self.begin,
+            end: self.end,
+            len: self.len,
+            begin_prev: NIL,
+            end_next: NIL,
+            marker: PhantomData,
+        }
+    }
+
+    /// Takes a vacant slot (or grows the slab) and moves `value` into it.
+    fn alloc_slot(&mut self, value: T) -> Index {
+        if self.free != NIL {
+            let index = self.free;
+            let slot = &mut self.slots[index as usize - 1];
+            self.free = mem::replace(&mut slot.xored, NIL);
+            slot.value.write(value);
+            index
+        } else {
+            assert!(
+                self.slots.len() < Index::MAX as usize,
+                "ArenaXorList cannot hold more than u32::MAX elements"
+            );
+            self.slots.push(Slot {
+                value: MaybeUninit::new(value),
+                xored: NIL,
+            });
+            self.slots.len() as Index
+        }
+    }
+
+    /// Moves the value out of an unlinked slot and puts the slot on the free chain.
+    fn free_slot(&mut self, index: Index) -> T {
+        let slot = &mut self.slots[index as usize - 1];
+        slot.xored = mem::replace(&mut self.free, index);
+        unsafe { slot.value.assume_init_read() }
+    }
+
+    #[inline]
+    fn slot(&self, index: Index) -> &Slot<T> {
+        &self.slots[index as usize - 1]
+    }
+
+    #[inline]
+    fn slot_mut(&mut self, index: Index) -> &mut Slot<T> {
+        &mut self.slots[index as usize - 1]
+    }
+
+    fn value(&self, index: Index) -> Option<&T> {
+        (index != NIL).then(|| unsafe { self.slot(index).value.assume_init_ref() })
+    }
+
+    fn value_mut(&mut self, index: Index) -> Option<&mut T> {
+        (index != NIL).then(|| unsafe { self.slot_mut(index).value.assume_init_mut() })
+    }
+}
+
+impl<T> Drop for ArenaXorList<T> {
+    fn drop(&mut self) {
+        if mem::needs_drop::<T>() {
+            while self.pop_front().is_some() {}
+        }
+    }
+}
+
+/// An iterator produced by [`ArenaXorList::iter`].
+pub struct Iter<'a, T> {
+    slots: &'a [Slot<T>],
+    begin: Index,
+    end: Index,
+    len: usize,
+    begin_prev: Index,
+    end_next: Index,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        let slot = &self.slots[self.begin as usize - 1];
+        let next = slot.xored ^ self.begin_prev;
+        self.begin_prev = mem::replace(&mut self.begin, next);
+        self.len -= 1;
+        Some(unsafe { slot.value.assume_init_ref() })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<&'a T> {
+        self.next_back()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        let slot = &self.slots[self.end as usize - 1];
+        let prev = slot.xored ^ self.end_next;
+        self.end_next = mem::replace(&mut self.end, prev);
+        self.len -= 1;
+        Some(unsafe { slot.value.assume_init_ref() })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter { ..*self }
+    }
+}
+
+/// An iterator produced by [`ArenaXorList::iter_mut`].
+pub struct IterMut<'a, T: 'a> {
+    // a raw pointer, as a `&mut [Slot<T>]` would be aliased by the references handed out
+    slots: *mut Slot<T>,
+    begin: Index,
+    end: Index,
+    len: usize,
+    begin_prev: Index,
+    end_next: Index,
+    marker: PhantomData<&'a mut Slot<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        unsafe {
+            let slot = &mut *self.slots.add(self.begin as usize - 1);
+            let next = slot.xored ^ self.begin_prev;
+            self.begin_prev = mem::replace(&mut self.begin, next);
+            self.len -= 1;
+            Some(slot.value.assume_init_mut())
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<&'a mut T> {
+        self.next_back()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        unsafe {
+            let slot = &mut *self.slots.add(self.end as usize - 1);
+            let prev = slot.xored ^ self.end_next;
+            self.end_next = mem::replace(&mut self.end, prev);
+            self.len -= 1;
+            Some(slot.value.assume_init_mut())
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+/// An iterator produced by [`ArenaXorList::into_iter`].
+pub struct IntoIter<T> {
+    list: ArenaXorList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T: PartialEq> PartialEq for ArenaXorList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for ArenaXorList<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for ArenaXorList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: Clone> Clone for ArenaXorList<T> {
+    fn clone(&self) -> Self {
+        let mut list = Self::with_capacity(self.len);
+        list.extend(self.iter().cloned());
+        list
+    }
+}
+
+impl<T> FromIterator<T> for ArenaXorList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for ArenaXorList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.slots.reserve(iter.size_hint().0);
+        iter.for_each(|elt| self.push_back(elt));
+    }
+}
+
+impl<T> IntoIterator for ArenaXorList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArenaXorList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ArenaXorList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::prelude::rust_2024::*;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::test_util::{assert_list, make_arena};
+
+    #[test]
+    fn push_and_pop_both_ends() {
+        let mut list = ArenaXorList::new();
+        list.push_back(2);
+        list.push_back(3);
+        list.push_front(1);
+        list.push_front(0);
+        assert_list(&list, &[0, 1, 2, 3]);
+        assert_eq!(list.front(), Some(&0));
+        assert_eq!(list.back(), Some(&3));
+
+        assert_eq!(list.pop_front(), Some(0));
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_back(), Some(1));
+        assert_eq!(list.pop_back(), None);
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn vacant_slots_are_reused() {
+        let mut list = ArenaXorList::with_capacity(4);
+        list.extend([1, 2, 3, 4]);
+        let capacity = list.capacity();
+
+        for i in 5..100 {
+            assert_eq!(list.pop_front(), Some(i - 4));
+            list.push_back(i);
+        }
+        assert_eq!(list.slots.len(), 4);
+        assert_eq!(list.capacity(), capacity);
+        assert_list(&list, &[96, 97, 98, 99]);
+    }
+
+    #[test]
+    fn front_and_back_mut() {
+        let mut list = make_arena(&[1, 2, 3]);
+        *list.front_mut().unwrap() = 10;
+        *list.back_mut().unwrap() = 30;
+        *list.push_back_mut(4) += 36;
+        *list.push_front_mut(0) -= 1;
+        assert_list(&list, &[-1, 10, 2, 30, 40]);
+    }
+
+    #[test]
+    fn iter_mut_from_both_ends() {
+        let mut list = make_arena(&[1, 2, 3, 4, 5]);
+        let mut iter = list.iter_mut();
+        *iter.next().unwrap() *= 10;
+        *iter.next_back().unwrap() *= 10;
+        *iter.next().unwrap() *= 10;
+        assert_eq!(iter.len(), 2);
+        assert_list(&list, &[10, 20, 3, 4, 50]);
+    }
+
+    #[test]
+    fn iterators_are_exported() {
+        let mut list = make_arena(&[1, 2, 3]);
+        let iter: crate::ArenaIter<'_, i32> = list.iter();
+        assert_eq!(iter.len(), 3);
+        let iter_mut: crate::ArenaIterMut<'_, i32> = list.iter_mut();
+        iter_mut.for_each(|x| *x += 1);
+        let into_iter: crate::ArenaIntoIter<i32> = list.into_iter();
+        assert_eq!(into_iter.collect::<Vec<_>>(), [2, 3, 4]);
+    }
+
+    #[test]
+    fn iter_interleaving_both_ends() {
+        let list = make_arena(&[1, 2, 3, 4, 5]);
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn split_off_at_every_position() {
+        for at in 0..=6 {
+            let mut list = make_arena(&[0, 1, 2, 3, 4, 5]);
+            let tail = list.split_off(at);
+
+            let expected: Vec<i32> = (0..6).collect();
+            assert_list(&list, &expected[..at]);
+            assert_list(&tail, &expected[at..]);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_off_panics_on_out_of_bounds() {
+        let mut list = make_arena(&[1, 2, 3]);
+        let _ = list.split_off(4);
+    }
+
+    #[test]
+    fn append_moves_the_shorter_list() {
+        let mut a = make_arena(&[1, 2]);
+        let mut b = make_arena(&[3, 4, 5]);
+        a.append(&mut b);
+        assert_list(&a, &[1, 2, 3, 4, 5]);
+        assert!(b.is_empty());
+
+        let mut c = make_arena(&[6]);
+        a.append(&mut c);
+        assert_list(&a, &[1, 2, 3, 4, 5, 6]);
+        assert!(c.is_empty());
+
+        let mut empty = ArenaXorList::new();
+        empty.append(&mut a);
+        assert_list(&empty, &[1, 2, 3, 4, 5, 6]);
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    fn reverse_in_constant_time() {
+        let mut list = make_arena(&[1, 2, 3]);
+        list.reverse();
+        assert_list(&list, &[3, 2, 1]);
+        list.push_back(0);
+        assert_list(&list, &[3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn list_is_relocatable() {
+        let list = make_arena(&[1, 2, 3]);
+        let moved = Box::new(list);
+        let cloned = (*moved).clone();
+        assert_list(&*moved, &[1, 2, 3]);
+        assert_list(&cloned, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn drops_every_element_once() {
+        let counter = Rc::new(());
+        let mut list = ArenaXorList::new();
+        for _ in 0..10 {
+            list.push_back(Rc::clone(&counter));
+        }
+        list.pop_front();
+        list.pop_back();
+        assert_eq!(Rc::strong_count(&counter), 9);
+
+        let mut iter = list.into_iter();
+        iter.next();
+        assert_eq!(Rc::strong_count(&counter), 8);
+        drop(iter);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn clear_and_equality() {
+        let mut list = make_arena(&[1, 2, 3]);
+        assert_eq!(list, make_arena(&[1, 2, 3]));
+        assert_ne!(list, make_arena(&[1, 2]));
+        assert!(list.contains(&2));
+        assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+
+        list.clear();
+        assert!(list.is_empty());
+        assert_eq!(list.front(), None);
+        list.push_back(4);
+        assert_list(&list, &[4]);
+    }
+}
@@ -0,0 +1,824 @@
+use core::fmt;
+use core::mem;
+use core::ptr::NonNull;
+
+use crate::{Global, LinkKey, LinkedList, Node, NodeAllocator, node_link, node_next};
+
+/// A cursor over a `LinkedList`.
+///
+/// A cursor points either at an element of the list or at a "ghost" non-element which sits
+/// between the back and the front of the list, so moving past either end wraps around through it.
+///
+/// Nodes only store the XOR of their neighbours, so besides the current node the cursor keeps
+/// the address of the node preceding it. That is all it needs to step in both directions.
+pub struct Cursor<'a, T: 'a, A: NodeAllocator = Global> {
+    index: usize,
+    current: Option<NonNull<Node<T>>>,
+    // address of the node before `current`, 0 if there is none (or we are at the ghost)
+    prev: usize,
+    list: &'a LinkedList<T, A>,
+}
+
+impl<T, A: NodeAllocator> Clone for Cursor<'_, T, A> {
+    fn clone(&self) -> Self {
+        let Cursor {
+            index,
+            current,
+            prev,
+            list,
+        } = *self;
+        Cursor {
+            index,
+            current,
+            prev,
+            list,
+        }
+    }
+}
+
+impl<T: fmt::Debug, A: NodeAllocator> fmt::Debug for Cursor<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Cursor")
+            .field(&self.list)
+            .field(&self.index())
+            .finish()
+    }
+}
+
+impl<'a, T, A: NodeAllocator> Cursor<'a, T, A> {
+    pub(crate) fn new_front(list: &'a LinkedList<T, A>) -> Self {
+        Cursor {
+            index: 0,
+            current: list.begin,
+            prev: 0,
+            list,
+        }
+    }
+
+    pub(crate) fn new_back(list: &'a LinkedList<T, A>) -> Self {
+        Cursor {
+            index: list.len.saturating_sub(1),
+            current: list.end,
+            prev: back_prev(list),
+            list,
+        }
+    }
+
+    /// Returns the index of the current element, or `None` at the ghost.
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    /// Moves to the next element. From the back of the list this moves to the ghost,
+    /// from the ghost to the front of the list.
+    pub fn move_next(&mut self) {
+        match self.current {
+            None => {
+                self.current = self.list.begin;
+                self.prev = 0;
+                self.index = 0;
+            }
+            Some(current) => {
+                self.current = NonNull::new(node_next(current, &mut self.prev, self.list.key));
+                self.index += 1;
+            }
+        }
+    }
+
+    /// Moves to the previous element. From the front of the list this moves to the ghost,
+    /// from the ghost to the back of the list.
+    pub fn move_prev(&mut self) {
+        match self.current {
+            None => {
+                self.current = self.list.end;
+                self.prev = back_prev(self.list);
+                self.index = self.list.len.saturating_sub(1);
+            }
+            Some(current) => {
+                self.current = prev_of(current, &mut self.prev, self.list.key);
+                self.index = match self.current {
+                    None => self.list.len,
+                    Some(_) => self.index - 1,
+                };
+            }
+        }
+    }
+
+    /// Returns the element the cursor points at, `None` at the ghost.
+    pub fn current(&self) -> Option<&'a T> {
+        self.current.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    /// Returns the element after the current one. At the ghost this is the front of the list.
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let next = match self.current {
+            None => self.list.begin,
+            Some(current) => {
+                NonNull::new(node_link(current, self.prev, self.list.key) as *mut Node<T>)
+            }
+        };
+        next.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    /// Returns the element before the current one. At the ghost this is the back of the list.
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        let prev = match self.current {
+            None => self.list.end,
+            Some(_) => NonNull::new(self.prev as *mut Node<T>),
+        };
+        prev.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    pub fn front(&self) -> Option<&'a T> {
+        self.list.front()
+    }
+
+    pub fn back(&self) -> Option<&'a T> {
+        self.list.back()
+    }
+
+    /// Returns the list this cursor walks over.
+    pub fn as_list(&self) -> &'a LinkedList<T, A> {
+        self.list
+    }
+}
+
+/// A cursor over a `LinkedList` with editing operations.
+///
+/// Like [`Cursor`] it points at an element or at the "ghost" non-element, but it can also
+/// insert, remove, splice and split the list around its position, all in O(1).
+pub struct CursorMut<'a, T: 'a, A: NodeAllocator = Global> {
+    index: usize,
+    current: Option<NonNull<Node<T>>>,
+    // address of the node before `current`, 0 if there is none (or we are at the ghost)
+    prev: usize,
+    list: &'a mut LinkedList<T, A>,
+}
+
+unsafe impl<T: Sync, A: NodeAllocator + Sync> Send for Cursor<'_, T, A> {}
+
+unsafe impl<T: Sync, A: NodeAllocator + Sync> Sync for Cursor<'_, T, A> {}
+
+unsafe impl<T: Send, A: NodeAllocator + Send> Send for CursorMut<'_, T, A> {}
+
+unsafe impl<T: Sync, A: NodeAllocator + Sync> Sync for CursorMut<'_, T, A> {}
+
+impl<T: fmt::Debug, A: NodeAllocator> fmt::Debug for CursorMut<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CursorMut")
+            .field(&self.list)
+            .field(&self.index())
+            .finish()
+    }
+}
+
+impl<'a, T, A: NodeAllocator> CursorMut<'a, T, A> {
+    pub(crate) fn new_front(list: &'a mut LinkedList<T, A>) -> Self {
+        CursorMut {
+            index: 0,
+            current: list.begin,
+            prev: 0,
+            list,
+        }
+    }
+
+    /// Creates a cursor at the element with index `at`, which must be in bounds.
+    pub(crate) fn new_at(list: &'a mut LinkedList<T, A>, at: usize) -> Self {
+        let (current, prev) = list.node_at(at);
+        CursorMut {
+            index: at,
+            current: Some(current),
+            prev,
+            list,
+        }
+    }
+
+    pub(crate) fn new_back(list: &'a mut LinkedList<T, A>) -> Self {
+        CursorMut {
+            index: list.len.saturating_sub(1),
+            current: list.end,
+            prev: back_prev(list),
+            list,
+        }
+    }
+
+    /// Returns the index of the current element, or `None` at the ghost.
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    /// Moves to the next element. From the back of the list this moves to the ghost,
+    /// from the ghost to the front of the list.
+    pub fn move_next(&mut self) {
+        match self.current {
+            None => {
+                self.current = self.list.begin;
+                self.prev = 0;
+                self.index = 0;
+            }
+            Some(current) => {
+                self.current = NonNull::new(node_next(current, &mut self.prev, self.list.key));
+                self.index += 1;
+            }
+        }
+    }
+
+    /// Moves to the previous element. From the front of the list this moves to the ghost,
+    /// from the ghost to the back of the list.
+    pub fn move_prev(&mut self) {
+        match self.current {
+            None => {
+                self.current = self.list.end;
+                self.prev = back_prev(self.list);
+                self.index = self.list.len.saturating_sub(1);
+            }
+            Some(current) => {
+                self.current = prev_of(current, &mut self.prev, self.list.key);
+                self.index = match self.current {
+                    None => self.list.len,
+                    Some(_) => self.index - 1,
+                };
+            }
+        }
+    }
+
+    /// Returns the element the cursor points at, `None` at the ghost.
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.current
+            .map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// Returns the element after the current one. At the ghost this is the front of the list.
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = match self.current {
+            None => self.list.begin,
+            Some(_) => NonNull::new(self.next_addr() as *mut Node<T>),
+        };
+        next.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// Returns the element before the current one. At the ghost this is the back of the list.
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = match self.current {
+            None => self.list.end,
+            Some(_) => NonNull::new(self.prev as *mut Node<T>),
+        };
+        prev.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// Returns a read-only cursor at the same position, borrowing this one.
+    pub fn as_cursor(&self) -> Cursor<'_, T, A> {
+        Cursor {
+            index: self.index,
+            current: self.current,
+            prev: self.prev,
+            list: self.list,
+        }
+    }
+
+    /// Inserts a new element right after the current one.
+    /// At the ghost the element becomes the new front of the list.
+    pub fn insert_after(&mut self, value: T) {
+        let node = self.list.new_node(value);
+        unsafe {
+            self.splice_after_chain(node, node, 1);
+        }
+    }
+
+    /// Inserts a new element right before the current one.
+    /// At the ghost the element becomes the new back of the list.
+    pub fn insert_before(&mut self, value: T) {
+        let node = self.list.new_node(value);
+        unsafe {
+            self.splice_before_chain(node, node, 1);
+        }
+    }
+
+    /// Removes the current element and returns it. The cursor moves to the next element
+    /// (or to the ghost if the removed one was the back). Does nothing at the ghost.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.unlink_current()?;
+        unsafe { Some(self.list.free_node(node)) }
+    }
+
+    /// Moves all elements of `list` right after the current one.
+    /// At the ghost they are prepended to the front of the list.
+    ///
+    /// Panics if the allocators of the two lists are not equal, see [`LinkedList::append`].
+    pub fn splice_after(&mut self, list: LinkedList<T, A>)
+    where
+        A: PartialEq,
+    {
+        assert!(
+            self.list.alloc == list.alloc,
+            "Cannot splice a list using another allocator"
+        );
+        if let Some((first, last, len)) = take_chain(list, self.list.key) {
+            unsafe {
+                self.splice_after_chain(first, last, len);
+            }
+        }
+    }
+
+    /// Moves all elements of `list` right before the current one.
+    /// At the ghost they are appended to the back of the list.
+    ///
+    /// Panics if the allocators of the two lists are not equal, see [`LinkedList::append`].
+    pub fn splice_before(&mut self, list: LinkedList<T, A>)
+    where
+        A: PartialEq,
+    {
+        assert!(
+            self.list.alloc == list.alloc,
+            "Cannot splice a list using another allocator"
+        );
+        if let Some((first, last, len)) = take_chain(list, self.list.key) {
+            unsafe {
+                self.splice_before_chain(first, last, len);
+            }
+        }
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.list.front()
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.list.back()
+    }
+
+    /// Address of the node after `current`. Must not be called at the ghost.
+    #[inline]
+    fn next_addr(&self) -> usize {
+        node_link(self.current.unwrap(), self.prev, self.list.key)
+    }
+
+    /// Takes the current node out of the list, leaving it a standalone single node chain.
+    pub(crate) fn unlink_current(&mut self) -> Option<NonNull<Node<T>>> {
+        let current = self.current?;
+        let next = self.next_addr();
+        unsafe {
+            self.list.unlink_chain(self.prev, next, current, current, 1);
+        }
+        // `prev` stays, it is now the predecessor of `next`; so does the index
+        self.current = NonNull::new(next as *mut Node<T>);
+        Some(current)
+    }
+
+    unsafe fn splice_after_chain(
+        &mut self,
+        first: NonNull<Node<T>>,
+        last: NonNull<Node<T>>,
+        len: usize,
+    ) {
+        unsafe {
+            match self.current {
+                None => {
+                    let begin = self.list.begin.map_or(0, |b| b.as_ptr() as usize);
+                    self.list.link_chain(0, begin, first, last, len);
+                    self.index = self.list.len;
+                }
+                Some(current) => {
+                    let next = self.next_addr();
+                    self.list
+                        .link_chain(current.as_ptr() as usize, next, first, last, len);
+                }
+            }
+        }
+    }
+
+    pub(crate) unsafe fn splice_before_chain(
+        &mut self,
+        first: NonNull<Node<T>>,
+        last: NonNull<Node<T>>,
+        len: usize,
+    ) {
+        unsafe {
+            match self.current {
+                None => {
+                    let end = self.list.end.map_or(0, |e| e.as_ptr() as usize);
+                    self.list.link_chain(end, 0, first, last, len);
+                    self.index = self.list.len;
+                }
+                Some(current) => {
+                    self.list
+                        .link_chain(self.prev, current.as_ptr() as usize, first, last, len);
+                    self.prev = last.as_ptr() as usize;
+                    self.index += len;
+                }
+            }
+        }
+    }
+}
+
+impl<T, A: NodeAllocator + Clone> CursorMut<'_, T, A> {
+    /// Like [`remove_current`](Self::remove_current), but hands the removed node back
+    /// as a single element list instead of deallocating it.
+    pub fn remove_current_as_list(&mut self) -> Option<LinkedList<T, A>> {
+        let node = self.unlink_current()?;
+        let mut list = self.list.new_sibling();
+        unsafe {
+            list.link_chain(0, 0, node, node, 1);
+        }
+        Some(list)
+    }
+
+    /// Splits the list after the current element, returning everything behind it.
+    /// At the ghost the whole list is returned.
+    pub fn split_after(&mut self) -> LinkedList<T, A> {
+        let Some(current) = self.current else {
+            self.index = 0;
+            return self.list.take_all();
+        };
+
+        let Some(first) = NonNull::new(self.next_addr() as *mut Node<T>) else {
+            return self.list.new_sibling();
+        };
+        let last = self.list.end.unwrap();
+        let len = self.list.len - self.index - 1;
+        unsafe {
+            self.list
+                .unlink_chain(current.as_ptr() as usize, 0, first, last, len);
+        }
+
+        let mut list = self.list.new_sibling();
+        unsafe {
+            list.link_chain(0, 0, first, last, len);
+        }
+        list
+    }
+
+    /// Splits the list before the current element, returning everything in front of it.
+    /// At the ghost the whole list is returned.
+    pub fn split_before(&mut self) -> LinkedList<T, A> {
+        let Some(current) = self.current else {
+            self.index = 0;
+            return self.list.take_all();
+        };
+
+        let Some(last) = NonNull::new(self.prev as *mut Node<T>) else {
+            return self.list.new_sibling();
+        };
+        let first = self.list.begin.unwrap();
+        let len = self.index;
+        unsafe {
+            self.list
+                .unlink_chain(0, current.as_ptr() as usize, first, last, len);
+        }
+        self.prev = 0;
+        self.index = 0;
+
+        let mut list = self.list.new_sibling();
+        unsafe {
+            list.link_chain(0, 0, first, last, len);
+        }
+        list
+    }
+}
+
+/// First node, last node and length of a detached chain.
+type Chain<T> = (NonNull<Node<T>>, NonNull<Node<T>>, usize);
+
+/// Dismantles a list into its raw chain, encoded with `key`, `None` if it is empty.
+fn take_chain<T, A: NodeAllocator>(mut list: LinkedList<T, A>, key: LinkKey) -> Option<Chain<T>> {
+    let first = list.begin.take()?;
+    unsafe { list.key.rekey_chain(first, key) };
+    let last = list.end.take().unwrap();
+    Some((first, last, mem::take(&mut list.len)))
+}
+
+/// Address of the node preceding the back of the list. The back has no successor,
+/// so its `xored` field holds exactly that address (mixed with the key).
+pub(crate) fn back_prev<T, A: NodeAllocator>(list: &LinkedList<T, A>) -> usize {
+    list.end.map(|end| node_link(end, 0, list.key)).unwrap_or(0)
+}
+
+/// Steps backwards from `current`: returns the node before it and rewrites `prev`
+/// to the address of the node before that one.
+#[inline]
+pub(crate) fn prev_of<T>(
+    current: NonNull<Node<T>>,
+    prev: &mut usize,
+    key: LinkKey,
+) -> Option<NonNull<Node<T>>> {
+    let before = NonNull::new(*prev as *mut Node<T>);
+    *prev = match before {
+        None => 0,
+        Some(before) => node_link(before, current.as_ptr() as usize, key),
+    };
+    before
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::LinkedList;
+    use crate::test_util::{assert_list, make_list};
+    use std::prelude::rust_2024::*;
+
+    #[test]
+    fn cursor_front_walks_forward_through_ghost() {
+        let list = make_list(&[1, 2, 3]);
+        let mut cursor = list.cursor_front();
+
+        assert_eq!(cursor.current(), Some(&1));
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&2));
+        assert_eq!(cursor.index(), Some(1));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&3));
+        assert_eq!(cursor.index(), Some(2));
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&1));
+        assert_eq!(cursor.index(), Some(0));
+    }
+
+    #[test]
+    fn cursor_back_walks_backward_through_ghost() {
+        let list = make_list(&[1, 2, 3]);
+        let mut cursor = list.cursor_back();
+
+        assert_eq!(cursor.current(), Some(&3));
+        assert_eq!(cursor.index(), Some(2));
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&2));
+        assert_eq!(cursor.index(), Some(1));
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&1));
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&3));
+        assert_eq!(cursor.index(), Some(2));
+    }
+
+    #[test]
+    fn cursor_changes_direction_mid_list() {
+        let list = make_list(&[1, 2, 3, 4, 5]);
+        let mut cursor = list.cursor_front();
+
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&4));
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&3));
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&2));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&3));
+        assert_eq!(cursor.index(), Some(2));
+    }
+
+    #[test]
+    fn cursor_peeks_neighbours() {
+        let list = make_list(&[1, 2, 3]);
+        let mut cursor = list.cursor_front();
+
+        assert_eq!(cursor.peek_prev(), None);
+        assert_eq!(cursor.peek_next(), Some(&2));
+        cursor.move_next();
+        assert_eq!(cursor.peek_prev(), Some(&1));
+        assert_eq!(cursor.peek_next(), Some(&3));
+        cursor.move_next();
+        assert_eq!(cursor.peek_prev(), Some(&2));
+        assert_eq!(cursor.peek_next(), None);
+        cursor.move_next();
+        // ghost
+        assert_eq!(cursor.peek_prev(), Some(&3));
+        assert_eq!(cursor.peek_next(), Some(&1));
+    }
+
+    #[test]
+    fn cursor_on_empty_list_stays_at_ghost() {
+        let list: LinkedList<i32> = LinkedList::new();
+        let mut cursor = list.cursor_front();
+
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(cursor.peek_prev(), None);
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+
+        let cursor = list.cursor_back();
+        assert_eq!(cursor.current(), None);
+    }
+
+    #[test]
+    fn cursor_single_element() {
+        let list = make_list(&[7]);
+        let mut cursor = list.cursor_back();
+
+        assert_eq!(cursor.current(), Some(&7));
+        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(cursor.peek_prev(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&7));
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+    }
+
+    #[test]
+    fn cursor_front_and_back_accessors() {
+        let list = make_list(&[1, 2, 3]);
+        let cursor = list.cursor_front();
+
+        assert_eq!(cursor.front(), Some(&1));
+        assert_eq!(cursor.back(), Some(&3));
+        assert_eq!(cursor.as_list().len(), 3);
+    }
+
+    #[test]
+    fn cursor_mut_insert_after_and_before() {
+        let mut list = make_list(&[1, 3]);
+        let mut cursor = list.cursor_front_mut();
+
+        cursor.insert_after(2);
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(cursor.peek_next(), Some(&mut 2));
+        cursor.insert_before(0);
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 0));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 3));
+        cursor.insert_after(4);
+        assert_eq!(cursor.peek_next(), Some(&mut 4));
+
+        assert_list(&list, &[0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn cursor_mut_insert_at_ghost() {
+        let mut list = make_list(&[1, 2]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+
+        cursor.insert_after(0);
+        cursor.insert_before(3);
+        assert_eq!(cursor.current(), None);
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 0));
+        assert_eq!(cursor.index(), Some(0));
+
+        assert_list(&list, &[0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn cursor_mut_insert_into_empty_list() {
+        let mut list = LinkedList::new();
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_before(2);
+        cursor.insert_after(1);
+        cursor.insert_before(3);
+        assert_list(&list, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn cursor_mut_remove_current_moves_to_next() {
+        let mut list = make_list(&[1, 2, 3, 4]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+
+        assert_eq!(cursor.remove_current(), Some(2));
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 1));
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(4));
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.remove_current(), None);
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!(cursor.current(), Some(&mut 3));
+
+        assert_list(&list, &[3]);
+    }
+
+    #[test]
+    fn cursor_mut_remove_everything() {
+        let mut list = make_list(&[1, 2, 3]);
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!(cursor.remove_current(), Some(3));
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), Some(2));
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!(cursor.current(), None);
+
+        assert!(list.is_empty());
+        assert!(list.front().is_none());
+        assert!(list.back().is_none());
+    }
+
+    #[test]
+    fn cursor_mut_remove_current_as_list() {
+        let mut list = make_list(&[1, 2, 3]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+
+        let removed = cursor.remove_current_as_list().unwrap();
+        assert_list(&removed, &[2]);
+        assert_list(&list, &[1, 3]);
+    }
+
+    #[test]
+    fn cursor_mut_splice_after_and_before() {
+        let mut list = make_list(&[1, 5]);
+        let mut cursor = list.cursor_front_mut();
+
+        cursor.splice_after(make_list(&[2, 3, 4]));
+        assert_eq!(cursor.current(), Some(&mut 1));
+        cursor.splice_before(make_list(&[-1, 0]));
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.peek_prev(), Some(&mut 0));
+        cursor.splice_before(LinkedList::new());
+        cursor.splice_after(LinkedList::new());
+
+        assert_list(&list, &[-1, 0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn cursor_mut_splice_at_ghost() {
+        let mut list = make_list(&[3]);
+        let mut cursor = list.cursor_back_mut();
+        cursor.move_next();
+
+        cursor.splice_after(make_list(&[1, 2]));
+        cursor.splice_before(make_list(&[4, 5]));
+        assert_eq!(cursor.current(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 5));
+        assert_eq!(cursor.index(), Some(4));
+
+        assert_list(&list, &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn cursor_mut_split_after() {
+        let mut list = make_list(&[1, 2, 3, 4]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+
+        let tail = cursor.split_after();
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.peek_next(), None);
+        assert_list(&tail, &[3, 4]);
+        assert_list(&list, &[1, 2]);
+
+        let mut cursor = list.cursor_back_mut();
+        assert!(cursor.split_after().is_empty());
+        cursor.move_next();
+        let all = cursor.split_after();
+        assert_list(&all, &[1, 2]);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn cursor_mut_split_before() {
+        let mut list = make_list(&[1, 2, 3, 4]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+
+        let head = cursor.split_before();
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.peek_prev(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+        assert_list(&head, &[1, 2]);
+        assert_list(&list, &[3, 4]);
+
+        let mut cursor = list.cursor_front_mut();
+        assert!(cursor.split_before().is_empty());
+        cursor.move_prev();
+        let all = cursor.split_before();
+        assert_list(&all, &[3, 4]);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn cursor_mut_as_cursor_shares_position() {
+        let mut list = make_list(&[1, 2, 3]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+
+        let view = cursor.as_cursor();
+        assert_eq!(view.current(), Some(&2));
+        assert_eq!(view.index(), Some(1));
+        assert_eq!(view.peek_prev(), Some(&1));
+        assert_eq!(view.peek_next(), Some(&3));
+    }
+}
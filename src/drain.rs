@@ -0,0 +1,266 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::mem;
+use core::ops::{Bound, RangeBounds};
+use core::ptr::NonNull;
+
+use crate::{Global, LinkedList, Node, NodeAllocator, node_link, node_next};
+
+impl<T, A: NodeAllocator> LinkedList<T, A> {
+    /// Removes the elements in `range` from the list and returns them as an iterator.
+    ///
+    /// The whole range is cut out of the list right away, so the list is already shortened
+    /// when this returns. The iterator yields the removed elements by value from either end,
+    /// the ones left when it is dropped are dropped with it.
+    ///
+    /// Panics if the start of the range is greater than its end or the end is greater than `len`.
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, A>
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1).expect("range start overflows"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1).expect("range end overflows"),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len,
+        };
+        assert!(
+            start <= end,
+            "drain range starts at {start} but ends at {end}"
+        );
+        assert!(end <= self.len, "Cannot drain up to a nonexistent index");
+
+        let len = end - start;
+        if len == 0 {
+            return Drain {
+                begin: None,
+                end: None,
+                len: 0,
+                list: self,
+            };
+        }
+
+        let (first, prev) = self.node_at(start);
+        // find `last` by walking on from `first`, unless one of the list ends is closer
+        let (last, last_prev) = if len - 1 <= (end - 1).min(self.len - end) {
+            let mut last_prev = prev;
+            let mut last = first;
+            for _ in 1..len {
+                last = NonNull::new(node_next(last, &mut last_prev, self.key)).unwrap();
+            }
+            (last, last_prev)
+        } else {
+            self.node_at(end - 1)
+        };
+        let next = node_link(last, last_prev, self.key);
+
+        unsafe {
+            self.unlink_chain(prev, next, first, last, len);
+        }
+        Drain {
+            begin: Some(first),
+            end: Some(last),
+            len,
+            list: self,
+        }
+    }
+}
+
+/// An iterator over the elements removed by [`LinkedList::drain`].
+///
+/// It owns the detached chain of nodes and hands them back to the list (to be freed or cached)
+/// one by one.
+pub struct Drain<'a, T: 'a, A: NodeAllocator = Global> {
+    begin: Option<NonNull<Node<T>>>,
+    end: Option<NonNull<Node<T>>>,
+    len: usize,
+    list: &'a mut LinkedList<T, A>,
+}
+
+impl<T, A: NodeAllocator> Drain<'_, T, A> {
+    /// Takes `node`, one of the ends of the remaining chain, off it and returns the new end.
+    /// The chain ends hold only their inner neighbour, so no outer address is needed.
+    fn unlink_end(&mut self, node: NonNull<Node<T>>) -> Option<NonNull<Node<T>>> {
+        self.len -= 1;
+        if self.len == 0 {
+            self.begin = None;
+            self.end = None;
+            return None;
+        }
+        unsafe {
+            let neighbour = node_link(node, 0, self.list.key) as *mut Node<T>;
+            (*neighbour).xored ^= node.as_ptr() as usize;
+            NonNull::new(neighbour)
+        }
+    }
+}
+
+impl<T, A: NodeAllocator> Iterator for Drain<'_, T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let node = self.begin?;
+        self.begin = self.unlink_end(node);
+        Some(unsafe { self.list.free_node(node) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T, A: NodeAllocator> DoubleEndedIterator for Drain<'_, T, A> {
+    fn next_back(&mut self) -> Option<T> {
+        let node = self.end?;
+        self.end = self.unlink_end(node);
+        Some(unsafe { self.list.free_node(node) })
+    }
+}
+
+impl<T, A: NodeAllocator> Drop for Drain<'_, T, A> {
+    fn drop(&mut self) {
+        /// Drops the rest of the elements if dropping one of them panics, like std's `Drain`.
+        struct DropGuard<'r, 'a, T, A: NodeAllocator>(&'r mut Drain<'a, T, A>);
+
+        impl<T, A: NodeAllocator> Drop for DropGuard<'_, '_, T, A> {
+            fn drop(&mut self) {
+                self.0.for_each(drop);
+            }
+        }
+
+        while let Some(value) = self.next() {
+            let guard = DropGuard(self);
+            drop(value);
+            mem::forget(guard);
+        }
+    }
+}
+
+impl<T, A: NodeAllocator> ExactSizeIterator for Drain<'_, T, A> {}
+
+impl<T, A: NodeAllocator> FusedIterator for Drain<'_, T, A> {}
+
+impl<T: fmt::Debug, A: NodeAllocator> fmt::Debug for Drain<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.len).finish()
+    }
+}
+
+unsafe impl<T: Send, A: NodeAllocator + Send> Send for Drain<'_, T, A> {}
+
+unsafe impl<T: Sync, A: NodeAllocator + Sync> Sync for Drain<'_, T, A> {}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+    use std::prelude::rust_2024::*;
+    use std::rc::Rc;
+
+    use crate::LinkedList;
+    use crate::test_util::{assert_list, make_list};
+
+    #[test]
+    fn drain_every_range() {
+        let values: Vec<i32> = (0..7).collect();
+        for start in 0..=7 {
+            for end in start..=7 {
+                let mut list = make_list(&values);
+                let drained: Vec<i32> = list.drain(start..end).collect();
+                assert_eq!(drained, values[start..end]);
+                assert_list(&list, &[&values[..start], &values[end..]].concat());
+
+                let mut list = make_list(&values);
+                let drained: Vec<i32> = list.drain(start..end).rev().collect();
+                assert!(drained.iter().rev().eq(&values[start..end]));
+            }
+        }
+    }
+
+    #[test]
+    fn drain_range_kinds() {
+        let mut list = make_list(&[0, 1, 2, 3, 4, 5]);
+        assert_eq!(list.drain(..2).collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(list.drain(2..).collect::<Vec<_>>(), vec![4, 5]);
+        assert_eq!(list.drain(0..=0).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(list.drain(..).collect::<Vec<_>>(), vec![3]);
+        assert!(list.is_empty());
+        assert_eq!(list.drain(..).next(), None);
+    }
+
+    #[test]
+    fn drain_from_both_ends() {
+        let mut list = make_list(&(0..10).collect::<Vec<_>>());
+        let mut drain = list.drain(2..8);
+        assert_eq!(drain.len(), 6);
+        assert_eq!(drain.next(), Some(2));
+        assert_eq!(drain.next_back(), Some(7));
+        assert_eq!(drain.next_back(), Some(6));
+        assert_eq!(drain.next(), Some(3));
+        assert_eq!(drain.next(), Some(4));
+        assert_eq!(drain.next_back(), Some(5));
+        assert_eq!(drain.next(), None);
+        assert_eq!(drain.next_back(), None);
+        drop(drain);
+        assert_list(&list, &[0, 1, 8, 9]);
+    }
+
+    #[test]
+    fn dropped_drain_drops_the_rest() {
+        let counter = Rc::new(());
+        let mut list: LinkedList<_> = (0..10).map(|_| Rc::clone(&counter)).collect();
+        let mut drain = list.drain(3..9);
+        drain.next();
+        drop(drain);
+        assert_eq!(list.len(), 4);
+        assert_eq!(Rc::strong_count(&counter), 5);
+    }
+
+    #[test]
+    fn dropped_drain_survives_a_panicking_drop() {
+        struct Bomb {
+            _counter: Rc<()>,
+            explodes: bool,
+        }
+
+        impl Drop for Bomb {
+            fn drop(&mut self) {
+                assert!(!self.explodes, "boom");
+            }
+        }
+
+        let counter = Rc::new(());
+        let mut list: LinkedList<_> = (0..10)
+            .map(|i| Bomb {
+                _counter: Rc::clone(&counter),
+                explodes: i == 4,
+            })
+            .collect();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let mut drain = list.drain(3..9);
+            drain.next();
+        }));
+        assert!(result.is_err());
+        // every drained element was dropped, the panicking one included
+        assert_eq!(list.len(), 4);
+        assert_eq!(Rc::strong_count(&counter), 5);
+        assert_eq!(list.check_integrity(), Ok(()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn drain_panics_past_the_end() {
+        make_list(&[1, 2, 3]).drain(1..4);
+    }
+
+    #[test]
+    #[should_panic]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn drain_panics_on_reversed_range() {
+        make_list(&[1, 2, 3]).drain(2..1);
+    }
+}
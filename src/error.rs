@@ -0,0 +1,167 @@
+use core::alloc::Layout;
+use core::error::Error;
+use core::fmt;
+
+/// Returned by the `try_` variants of positional methods when the index is out of bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexOutOfBounds {
+    pub index: usize,
+    pub len: usize,
+}
+
+impl fmt::Display for IndexOutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "index {} is out of bounds for a list of length {}",
+            self.index, self.len
+        )
+    }
+}
+
+impl Error for IndexOutOfBounds {}
+
+/// Returned by a [`NodeAllocator`](crate::NodeAllocator) that could not provide the requested memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocError;
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+impl Error for AllocError {}
+
+/// Returned by the fallible `try_` methods when a node could not be allocated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryReserveError {
+    pub(crate) layout: Layout,
+}
+
+impl TryReserveError {
+    /// Returns the layout of the allocation that failed.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "memory allocation of {} bytes failed",
+            self.layout.size()
+        )
+    }
+}
+
+impl Error for TryReserveError {}
+
+/// Returned by [`LinkedList::try_insert`](crate::LinkedList::try_insert).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertError {
+    OutOfBounds(IndexOutOfBounds),
+    Alloc(TryReserveError),
+}
+
+impl From<IndexOutOfBounds> for InsertError {
+    fn from(err: IndexOutOfBounds) -> Self {
+        InsertError::OutOfBounds(err)
+    }
+}
+
+impl From<TryReserveError> for InsertError {
+    fn from(err: TryReserveError) -> Self {
+        InsertError::Alloc(err)
+    }
+}
+
+impl fmt::Display for InsertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InsertError::OutOfBounds(err) => err.fmt(f),
+            InsertError::Alloc(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for InsertError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InsertError::OutOfBounds(err) => Some(err),
+            InsertError::Alloc(err) => Some(err),
+        }
+    }
+}
+
+/// One of the two ends of a list, see [`IntegrityError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListEnd {
+    Front,
+    Back,
+}
+
+impl ListEnd {
+    pub(crate) fn opposite(self) -> Self {
+        match self {
+            ListEnd::Front => ListEnd::Back,
+            ListEnd::Back => ListEnd::Front,
+        }
+    }
+}
+
+impl fmt::Display for ListEnd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ListEnd::Front => f.write_str("front"),
+            ListEnd::Back => f.write_str("back"),
+        }
+    }
+}
+
+/// Returned by [`LinkedList::check_integrity`](crate::LinkedList::check_integrity),
+/// describes the first place where the chain of nodes is broken.
+///
+/// Indexes are counted from the end the walk started at, links are the raw decoded addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrityError {
+    /// The list has only one of its ends set, or has them set (or not) although `len` says otherwise.
+    Ends { len: usize },
+    /// Walking from `from`, the node at `index` links on to `link`, which is null or not
+    /// a node address, before `len` nodes were visited.
+    BrokenLink {
+        from: ListEnd,
+        index: usize,
+        link: usize,
+    },
+    /// Walking from `from`, the `len`-th node is not the other end of the list.
+    MissedEnd { from: ListEnd, len: usize },
+    /// The node at `end` links to `link` on its outer side as well, instead of only to its
+    /// single neighbour.
+    Endpoint { end: ListEnd, link: usize },
+}
+
+impl fmt::Display for IntegrityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            IntegrityError::Ends { len } => {
+                write!(f, "list ends don't match its length of {len}")
+            }
+            IntegrityError::BrokenLink { from, index, link } => write!(
+                f,
+                "node {index} from the {from} links to invalid address {link:#x}"
+            ),
+            IntegrityError::MissedEnd { from, len } => write!(
+                f,
+                "walking {len} nodes from the {from} doesn't reach the {}",
+                from.opposite()
+            ),
+            IntegrityError::Endpoint { end, link } => {
+                write!(f, "{end} node links past the end to {link:#x}")
+            }
+        }
+    }
+}
+
+impl Error for IntegrityError {}
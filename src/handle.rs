@@ -0,0 +1,306 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{LinkedList, Node, NodeAllocator, node_link};
+
+/// Points at one node of a [`LinkedList`], for removing or moving it in O(1) without a walk.
+///
+/// A node on its own can't be unlinked, it only knows the XOR of its neighbours. So the handle
+/// records the address of one of them too (none for a node pushed into an empty list), which
+/// can be trusted only as long as nothing was taken out of the list. Each handle is therefore
+/// tagged with the list that made it and that list's generation, which changes whenever
+/// a node is removed or relinked: by `pop_*`, `remove`, `insert`, `append`, `split_off`,
+/// `sort`, a cursor edit, or by a handle method itself. Only pushes at either end (and
+/// `reverse`) keep the handles.
+///
+/// The methods taking a handle check the tags first, in O(1), and return `None` for a handle
+/// of another list or of an older generation, so a stale handle is never followed.
+///
+/// # Limitations
+///
+/// Handles don't survive moving nodes around, so they can't back an LRU cache that keeps
+/// a handle to every entry. An XOR list has no way to find a node's neighbours after
+/// the nodes around it changed, other than walking to it.
+///
+/// ```
+/// use xor_ll::LinkedList;
+///
+/// let mut lru = LinkedList::new();
+/// let a = lru.push_front_handle("a");
+/// let b = lru.push_front_handle("b");
+/// // `a` is used again, which relinks nodes
+/// let a = lru.move_to_front(a).unwrap();
+/// assert!(lru.is_valid_handle(&a));
+/// // `b` can't be evicted through its handle anymore
+/// assert!(!lru.is_valid_handle(&b));
+/// assert_eq!(lru.remove_handle(b), None);
+/// assert_eq!(lru.len(), 2);
+/// ```
+pub struct NodeHandle<T> {
+    node: NonNull<Node<T>>,
+    // address of a node next to `node`, 0 if there was none when the handle was made
+    neighbour: usize,
+    list: usize,
+    generation: u64,
+    marker: PhantomData<*const T>,
+}
+
+impl<T> fmt::Debug for NodeHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeHandle")
+            .field("node", &self.node)
+            .field("neighbour", &(self.neighbour as *const ()))
+            .field("list", &self.list)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+// A handle is just an address, it can only be dereferenced through the list holding the node.
+unsafe impl<T: Send> Send for NodeHandle<T> {}
+
+unsafe impl<T: Sync> Sync for NodeHandle<T> {}
+
+impl<T, A: NodeAllocator> LinkedList<T, A> {
+    /// Like [`push_back`](Self::push_back), but returns a handle to the new node.
+    pub fn push_back_handle(&mut self, value: T) -> NodeHandle<T> {
+        let neighbour = self.end.map_or(0, |end| end.as_ptr() as usize);
+        let node = self.new_node(value);
+        unsafe { self.push_back_inner(node) };
+        self.new_handle(node, neighbour)
+    }
+
+    /// Like [`push_front`](Self::push_front), but returns a handle to the new node.
+    pub fn push_front_handle(&mut self, value: T) -> NodeHandle<T> {
+        let neighbour = self.begin.map_or(0, |begin| begin.as_ptr() as usize);
+        let node = self.new_node(value);
+        unsafe { self.push_front_inner(node) };
+        self.new_handle(node, neighbour)
+    }
+
+    /// Returns a reference to the element of `handle`'s node, or `None` if the handle is stale.
+    pub fn value_of(&self, handle: &NodeHandle<T>) -> Option<&T> {
+        self.is_current(handle)
+            .then(|| unsafe { &(*handle.node.as_ptr()).value })
+    }
+
+    /// Returns a mutable reference to the element of `handle`'s node, or `None` if the handle
+    /// is stale.
+    pub fn value_of_mut(&mut self, handle: &NodeHandle<T>) -> Option<&mut T> {
+        self.is_current(handle)
+            .then(|| unsafe { &mut (*handle.node.as_ptr()).value })
+    }
+
+    /// Removes the node of `handle` from the list in O(1) and returns its element,
+    /// or `None` if the handle is stale.
+    pub fn remove_handle(&mut self, handle: NodeHandle<T>) -> Option<T> {
+        let (prev, next) = self.handle_links(&handle)?;
+        unsafe {
+            self.unlink_chain(prev, next, handle.node, handle.node, 1);
+            Some(self.free_node(handle.node))
+        }
+    }
+
+    /// Moves the node of `handle` to the front of the list in O(1) and returns a new handle
+    /// to it, or `None` if the handle is stale. All other handles go stale.
+    pub fn move_to_front(&mut self, handle: NodeHandle<T>) -> Option<NodeHandle<T>> {
+        let (prev, next) = self.handle_links(&handle)?;
+        if prev == 0 {
+            return Some(self.new_handle(handle.node, next));
+        }
+        let begin = self.begin.unwrap().as_ptr() as usize;
+        unsafe {
+            self.unlink_chain(prev, next, handle.node, handle.node, 1);
+            self.link_chain(0, begin, handle.node, handle.node, 1);
+        }
+        Some(self.new_handle(handle.node, begin))
+    }
+
+    /// Moves the node of `handle` to the back of the list in O(1) and returns a new handle
+    /// to it, or `None` if the handle is stale. All other handles go stale.
+    pub fn move_to_back(&mut self, handle: NodeHandle<T>) -> Option<NodeHandle<T>> {
+        let (prev, next) = self.handle_links(&handle)?;
+        if next == 0 {
+            return Some(self.new_handle(handle.node, prev));
+        }
+        let end = self.end.unwrap().as_ptr() as usize;
+        unsafe {
+            self.unlink_chain(prev, next, handle.node, handle.node, 1);
+            self.link_chain(end, 0, handle.node, handle.node, 1);
+        }
+        Some(self.new_handle(handle.node, end))
+    }
+
+    /// Checks whether `handle` can be used with this list, in O(1).
+    pub fn is_valid_handle(&self, handle: &NodeHandle<T>) -> bool {
+        self.handle_links(handle).is_some()
+    }
+
+    /// Bumps the generation, making every handle given out so far stale.
+    #[inline]
+    pub(crate) fn invalidate_handles(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    fn new_handle(&mut self, node: NonNull<Node<T>>, neighbour: usize) -> NodeHandle<T> {
+        if self.id == 0 {
+            self.id = next_list_id();
+        }
+        NodeHandle {
+            node,
+            neighbour,
+            list: self.id,
+            generation: self.generation,
+            marker: PhantomData,
+        }
+    }
+
+    /// Whether `handle` was made by this list in its current generation, so its node
+    /// and the recorded neighbour are still in the list.
+    fn is_current(&self, handle: &NodeHandle<T>) -> bool {
+        handle.list == self.id && handle.generation == self.generation
+    }
+
+    /// Returns the addresses of the nodes before and after `handle`'s node,
+    /// or `None` if the handle is stale.
+    fn handle_links(&self, handle: &NodeHandle<T>) -> Option<(usize, usize)> {
+        if !self.is_current(handle) {
+            return None;
+        }
+        // Only pushes at the ends happened since the handle was made, and those never get
+        // in between two nodes, so the recorded neighbour is still right next to the node.
+        let node = handle.node;
+        let other = node_link(node, handle.neighbour, self.key);
+        if handle.neighbour != 0 && other != 0 {
+            // in the middle, where unlinking doesn't care which side is which
+            return Some((handle.neighbour, other));
+        }
+
+        let inner = handle.neighbour ^ other;
+        if self.begin == Some(node) {
+            Some((0, inner))
+        } else if self.end == Some(node) {
+            Some((inner, 0))
+        } else {
+            // made without a neighbour, and pushes buried the node since
+            None
+        }
+    }
+}
+
+/// Returns an id no other list got, for tagging handles.
+fn next_list_id() -> usize {
+    static NEXT: AtomicUsize = AtomicUsize::new(1);
+    NEXT.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |id| id.checked_add(1))
+        .expect("ran out of list ids for NodeHandle")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::prelude::rust_2024::*;
+
+    use crate::LinkedList;
+    use crate::test_util::assert_list;
+
+    #[test]
+    fn push_and_remove_by_handle() {
+        let mut list = LinkedList::new();
+        let two = list.push_back_handle(2);
+        let three = list.push_back_handle(3);
+        let one = list.push_front_handle(1);
+        let zero = list.push_front_handle(0);
+        assert_list(&list, &[0, 1, 2, 3]);
+        for handle in [&zero, &one, &three] {
+            assert!(list.is_valid_handle(handle));
+        }
+        // pushed into an empty list, so it recorded no neighbour, but it's not at an end anymore
+        assert!(!list.is_valid_handle(&two));
+        assert_eq!(list.value_of(&two), Some(&2));
+        *list.value_of_mut(&three).unwrap() = 30;
+
+        assert_eq!(list.remove_handle(one), Some(1));
+        assert_list(&list, &[0, 2, 30]);
+        // the removal made every other handle stale
+        assert!(!list.is_valid_handle(&zero));
+        assert_eq!(list.value_of(&three), None);
+        assert_eq!(list.remove_handle(three), None);
+        assert_list(&list, &[0, 2, 30]);
+    }
+
+    #[test]
+    fn move_to_front_and_back() {
+        let mut list = LinkedList::new();
+        let mut handles: Vec<_> = (0..5).map(|i| list.push_back_handle(i)).collect();
+        let h = handles.remove(3);
+        let h = list.move_to_front(h).unwrap();
+        assert_list(&list, &[3, 0, 1, 2, 4]);
+        // already there
+        let h = list.move_to_front(h).unwrap();
+        assert_list(&list, &[3, 0, 1, 2, 4]);
+        let h = list.move_to_back(h).unwrap();
+        assert_list(&list, &[0, 1, 2, 4, 3]);
+        let h = list.move_to_back(h).unwrap();
+
+        // pushes keep the handle, and it works from the middle too
+        list.push_back(5);
+        list.push_front(-1);
+        let h = list.move_to_front(h).unwrap();
+        assert_list(&list, &[3, -1, 0, 1, 2, 4, 5]);
+        list.push_front(-2);
+        assert_eq!(list.remove_handle(h), Some(3));
+        assert_list(&list, &[-2, -1, 0, 1, 2, 4, 5]);
+    }
+
+    #[test]
+    fn handles_survive_pushes_and_reverse() {
+        let mut list = LinkedList::new();
+        let only = list.push_back_handle(1);
+        let back = list.push_back_handle(2);
+        list.push_back(3);
+        list.reverse();
+        list.push_back(4);
+        // `only` recorded no neighbour and was buried, `back` kept its neighbour
+        assert!(!list.is_valid_handle(&only));
+        assert_eq!(list.remove_handle(back), Some(2));
+        assert_list(&list, &[3, 1, 4]);
+    }
+
+    #[test]
+    fn handles_of_other_lists_are_rejected() {
+        let mut list = LinkedList::new();
+        let mut other = LinkedList::new();
+        let handle = list.push_back_handle(1);
+        let foreign = other.push_back_handle(1);
+        assert!(!other.is_valid_handle(&handle));
+        assert!(!list.is_valid_handle(&foreign));
+
+        other.append(&mut list);
+        assert!(!other.is_valid_handle(&handle));
+        assert_eq!(other.remove_handle(handle), None);
+        // a list made from the nodes of another doesn't take its handles over
+        let mut rest = other.split_off(0);
+        assert_eq!(rest.remove_handle(foreign), None);
+        assert_list(&rest, &[1, 1]);
+    }
+
+    #[test]
+    fn every_relinking_operation_invalidates() {
+        let ops: [fn(&mut LinkedList<i32>); 7] = [
+            |list| _ = list.pop_back(),
+            |list| list.insert(1, 9),
+            |list| _ = list.remove(1),
+            |list| list.rotate_left(1),
+            |list| list.sort(),
+            |list| list.retain(|&x| x != 1),
+            |list| list.cursor_front_mut().insert_after(9),
+        ];
+        for op in ops {
+            let mut list = LinkedList::from([1, 2]);
+            let handle = list.push_back_handle(3);
+            op(&mut list);
+            assert!(!list.is_valid_handle(&handle));
+        }
+    }
+}
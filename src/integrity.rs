@@ -0,0 +1,190 @@
+use core::mem;
+use core::ptr::NonNull;
+
+use crate::{IntegrityError, LinkKey, LinkedList, ListEnd, Node, NodeAllocator};
+
+impl<T, A: NodeAllocator> LinkedList<T, A> {
+    /// Checks that the nodes form a proper chain: walking from either end has to visit
+    /// exactly `len` nodes and stop at the other end, whose link must point only back
+    /// at its single neighbour.
+    ///
+    /// Meant for tracking down memory corruption, which in an XOR list otherwise shows up as
+    /// a crash far away from its cause. The walk can only follow the links, so it catches
+    /// null and misaligned ones, but a link into unrelated memory may still crash it.
+    /// Takes O(len).
+    ///
+    /// With the `debug-invariants` feature this runs after every operation that relinks nodes,
+    /// panicking on the first error. That makes every such operation O(n), and loops of them
+    /// like `clear` or `retain` O(n²), so the feature is meant for debugging only.
+    pub fn check_integrity(&self) -> Result<(), IntegrityError> {
+        match (self.begin, self.end) {
+            (None, None) if self.len == 0 => Ok(()),
+            (Some(begin), Some(end)) if self.len > 0 => {
+                walk(begin, end, self.len, self.key, ListEnd::Front)?;
+                walk(end, begin, self.len, self.key, ListEnd::Back)
+            }
+            _ => Err(IntegrityError::Ends { len: self.len }),
+        }
+    }
+
+    /// Panics if [`check_integrity`](Self::check_integrity) fails, with the `debug-invariants`
+    /// feature. Does nothing otherwise.
+    #[inline(always)]
+    pub(crate) fn debug_check_integrity(&self) {
+        #[cfg(feature = "debug-invariants")]
+        if let Err(err) = self.check_integrity() {
+            panic!("corrupted list: {err}");
+        }
+    }
+}
+
+/// Walks `len` nodes from `start`, see [`LinkedList::check_integrity`].
+fn walk<T>(
+    start: NonNull<Node<T>>,
+    target: NonNull<Node<T>>,
+    len: usize,
+    key: LinkKey,
+    from: ListEnd,
+) -> Result<(), IntegrityError> {
+    let mut prev = 0;
+    let mut current = start;
+    // decoded by hand, `node_next` would panic on a bad link in hardened mode
+    let next = |node: NonNull<Node<T>>, prev: &mut usize| {
+        let link = unsafe { (*node.as_ptr()).xored } ^ *prev ^ key.bits();
+        *prev = node.as_ptr() as usize;
+        link
+    };
+    for index in 0..len - 1 {
+        let link = next(current, &mut prev);
+        if link == 0 || !link.is_multiple_of(mem::align_of::<Node<T>>()) {
+            return Err(IntegrityError::BrokenLink { from, index, link });
+        }
+        current = NonNull::new(link as *mut Node<T>).unwrap();
+    }
+
+    if current != target {
+        return Err(IntegrityError::MissedEnd { from, len });
+    }
+    let link = next(current, &mut prev);
+    if link != 0 {
+        return Err(IntegrityError::Endpoint {
+            end: from.opposite(),
+            link,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::prelude::rust_2024::*;
+
+    use crate::test_util::make_list;
+    use crate::{IntegrityError, ListEnd};
+
+    #[test]
+    fn intact_lists_pass() {
+        for len in 0..6 {
+            let mut list = make_list(&(0..len).collect::<Vec<_>>());
+            assert_eq!(list.check_integrity(), Ok(()));
+            list.reverse();
+            list.rotate_left(list.len() / 2);
+            list.push_front(-1);
+            list.remove(list.len() / 2);
+            let mut back = list.split_off(list.len() / 2);
+            assert_eq!(list.check_integrity(), Ok(()));
+            assert_eq!(back.check_integrity(), Ok(()));
+            list.append(&mut back);
+            assert_eq!(list.check_integrity(), Ok(()));
+            assert_eq!(back.check_integrity(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn wrong_length() {
+        let mut list = make_list(&[1, 2, 3]);
+        list.len = 4;
+        let err = list.check_integrity().unwrap_err();
+        assert_eq!(
+            err,
+            IntegrityError::BrokenLink {
+                from: ListEnd::Front,
+                index: 2,
+                link: 0
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "node 2 from the front links to invalid address 0x0"
+        );
+
+        list.len = 2;
+        assert_eq!(
+            list.check_integrity(),
+            Err(IntegrityError::MissedEnd {
+                from: ListEnd::Front,
+                len: 2
+            })
+        );
+
+        list.len = 0;
+        assert_eq!(list.check_integrity(), Err(IntegrityError::Ends { len: 0 }));
+        list.len = 3;
+    }
+
+    #[test]
+    fn corrupted_link() {
+        let list = make_list(&[1, 2, 3, 4, 5]);
+        let (middle, _) = list.node_at(2);
+        unsafe { (*middle.as_ptr()).xored ^= 1 };
+        assert!(matches!(
+            list.check_integrity(),
+            Err(IntegrityError::BrokenLink {
+                from: ListEnd::Front,
+                index: 2,
+                ..
+            })
+        ));
+        unsafe { (*middle.as_ptr()).xored ^= 1 };
+        assert_eq!(list.check_integrity(), Ok(()));
+    }
+
+    #[test]
+    fn corrupted_endpoint() {
+        let list = make_list(&[1, 2, 3]);
+        let (begin, end) = (list.begin.unwrap(), list.end.unwrap());
+        let stray = begin.as_ptr() as usize;
+        unsafe { (*end.as_ptr()).xored ^= stray };
+        assert_eq!(
+            list.check_integrity(),
+            Err(IntegrityError::Endpoint {
+                end: ListEnd::Back,
+                link: stray
+            })
+        );
+        unsafe { (*end.as_ptr()).xored ^= stray };
+
+        let single = make_list(&[1]);
+        let node = single.begin.unwrap();
+        unsafe { (*node.as_ptr()).xored ^= stray };
+        assert!(single.check_integrity().is_err());
+        unsafe { (*node.as_ptr()).xored ^= stray };
+    }
+
+    #[test]
+    fn mismatched_ends() {
+        let mut list = make_list(&[1, 2, 3]);
+        let end = list.end;
+        list.end = list.begin;
+        assert_eq!(
+            list.check_integrity(),
+            Err(IntegrityError::MissedEnd {
+                from: ListEnd::Front,
+                len: 3
+            })
+        );
+        list.end = None;
+        assert_eq!(list.check_integrity(), Err(IntegrityError::Ends { len: 3 }));
+        list.end = end;
+    }
+}
@@ -0,0 +1,71 @@
+use std::io::{self, Read, Write};
+
+use crate::{LinkedList, NodeAllocator};
+
+/// Reads by removing bytes from the front of the list, like `VecDeque<u8>` does.
+impl<A: NodeAllocator> Read for LinkedList<u8, A> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.len);
+        for byte in &mut buf[..n] {
+            *byte = self.pop_front().unwrap();
+        }
+        Ok(n)
+    }
+}
+
+/// Writes by appending bytes to the back of the list.
+///
+/// If a node can't be allocated, the bytes written so far are reported, or an
+/// [`OutOfMemory`](io::ErrorKind::OutOfMemory) error if there are none.
+impl<A: NodeAllocator> Write for LinkedList<u8, A> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for (written, &byte) in buf.iter().enumerate() {
+            if self.try_push_back(byte).is_err() {
+                if written == 0 {
+                    return Err(io::ErrorKind::OutOfMemory.into());
+                }
+                return Ok(written);
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::prelude::rust_2024::*;
+
+    use crate::LinkedList;
+
+    #[test]
+    fn write_then_read_back() {
+        let mut list = LinkedList::new();
+        list.write_all(b"hello ").unwrap();
+        write!(list, "{}", 42).unwrap();
+        assert_eq!(list.len(), 8);
+
+        let mut buf = [0; 5];
+        assert_eq!(list.read(&mut buf).unwrap(), 5);
+        assert_eq!(&buf, b"hello");
+
+        let mut rest = String::new();
+        list.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, " 42");
+        assert!(list.is_empty());
+        assert_eq!(list.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn copy_between_lists() {
+        let mut source: LinkedList<u8> = (0..=255).collect();
+        let mut target = LinkedList::new();
+        assert_eq!(std::io::copy(&mut source, &mut target).unwrap(), 256);
+        assert!(source.is_empty());
+        assert!(target.iter().copied().eq(0..=255));
+    }
+}
@@ -0,0 +1,190 @@
+use core::ptr::NonNull;
+
+use crate::{Node, node_next};
+
+/// The secret every link of a list is XORed with in hardened mode (the `hardened` feature),
+/// like glibc's safe-linking does for its free lists.
+///
+/// A node's `xored` then holds `prev ^ next ^ key`, and a cached node's link is `next ^ key`.
+/// Updating a link by XORing addresses in and out works the same as before, only reading
+/// an address out of a link and writing one from scratch go through the key. Links written
+/// by someone who doesn't know the key decode to garbage, which is caught by an alignment
+/// check (the lowest bit of the key is always set) and panics instead of being followed.
+///
+/// Without the feature this is zero-sized and all of it compiles down to plain XOR links.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct LinkKey {
+    #[cfg(feature = "hardened")]
+    bits: usize,
+}
+
+impl LinkKey {
+    /// Returns a fresh random key, or the only one there is without the `hardened` feature.
+    pub(crate) fn new() -> Self {
+        LinkKey {
+            #[cfg(feature = "hardened")]
+            bits: random_bits() | 1,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn bits(self) -> usize {
+        #[cfg(feature = "hardened")]
+        return self.bits;
+        #[cfg(not(feature = "hardened"))]
+        0
+    }
+
+    /// Turns an address (or XOR of two addresses) into a link.
+    #[inline(always)]
+    pub(crate) fn encode(self, addr: usize) -> usize {
+        addr ^ self.bits()
+    }
+
+    /// Turns a link back into an address (or XOR of two addresses).
+    ///
+    /// In hardened mode, panics if the result is not aligned for a node - the link was
+    /// not written with this key.
+    #[inline(always)]
+    #[cfg_attr(not(feature = "hardened"), allow(clippy::extra_unused_type_parameters))]
+    pub(crate) fn decode<T>(self, link: usize) -> usize {
+        let addr = link ^ self.bits();
+        #[cfg(feature = "hardened")]
+        assert!(
+            addr.is_multiple_of(align_of::<Node<T>>()),
+            "corrupted list link: {addr:#x} is not a node address"
+        );
+        addr
+    }
+
+    /// Re-encodes the standalone chain starting at `first` from this key to `to`, so it can be
+    /// linked into a list using `to`. O(chain length), unless the keys are the same.
+    pub(crate) unsafe fn rekey_chain<T>(self, first: NonNull<Node<T>>, to: LinkKey) {
+        if self == to {
+            return;
+        }
+        let mut prev = 0;
+        let mut current = Some(first);
+        while let Some(node) = current {
+            current = NonNull::new(node_next(node, &mut prev, self));
+            unsafe { (*node.as_ptr()).xored ^= self.bits() ^ to.bits() };
+        }
+    }
+}
+
+#[cfg(all(feature = "hardened", feature = "std"))]
+fn random_bits() -> usize {
+    use core::hash::{BuildHasher, Hasher};
+    use std::collections::hash_map::RandomState;
+
+    // every RandomState gets different SipHash keys, so hashing nothing is random enough
+    RandomState::new().build_hasher().finish() as usize
+}
+
+/// There is no source of entropy without std, so this mixes a global counter with a stack
+/// and a code address, which differ between runs only as far as ASLR makes them.
+#[cfg(all(feature = "hardened", not(feature = "std")))]
+fn random_bits() -> usize {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let local = 0u8;
+    let mut x = COUNTER.fetch_add(1, Ordering::Relaxed) as u64
+        ^ (&local as *const u8 as u64).rotate_left(32)
+        ^ random_bits as fn() -> usize as usize as u64;
+    // splitmix64 finalizer
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    (x ^ (x >> 31)) as usize
+}
+
+#[cfg(all(test, feature = "hardened"))]
+mod tests {
+    use core::ptr::NonNull;
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+    use std::vec::Vec;
+
+    use crate::test_util::make_list;
+
+    #[test]
+    fn links_are_not_plain_addresses() {
+        let list = make_list(&[1, 2]);
+        let (begin, end) = (list.begin.unwrap(), list.end.unwrap());
+        unsafe {
+            assert_ne!((*begin.as_ptr()).xored, end.as_ptr() as usize);
+            assert_ne!((*end.as_ptr()).xored, begin.as_ptr() as usize);
+        }
+        assert_ne!(list.key, make_list(&[1, 2]).key);
+    }
+
+    #[test]
+    fn forged_link_panics() {
+        let list = make_list(&[1, 2, 3]);
+        let (begin, end) = (list.begin.unwrap(), list.end.unwrap());
+        let (middle, _) = list.node_at(1);
+        let saved = unsafe { (*middle.as_ptr()).xored };
+        // what a plain XOR list would hold
+        unsafe { (*middle.as_ptr()).xored = begin.as_ptr() as usize ^ end.as_ptr() as usize };
+        assert!(catch_unwind(AssertUnwindSafe(|| list.iter().count())).is_err());
+        assert!(catch_unwind(AssertUnwindSafe(|| list.iter().rev().count())).is_err());
+        assert!(list.check_integrity().is_err());
+        unsafe { (*middle.as_ptr()).xored = saved };
+        assert_eq!(list.iter().count(), 3);
+    }
+
+    #[test]
+    fn chains_move_between_lists_with_different_keys() {
+        let mut list = make_list(&[1, 2, 3]);
+        let mut other = make_list(&[4, 5, 6]);
+        assert_ne!(list.key, other.key);
+
+        list.append(&mut other);
+        let mut cursor = list.cursor_front_mut();
+        cursor.splice_after(make_list(&[7, 8]));
+        cursor.move_next();
+        cursor.move_next();
+        cursor.splice_before(make_list(&[9]));
+        let mut sorted = make_list(&[0, 10]);
+        sorted.merge(&mut make_list(&[5, 20]));
+
+        for list in [&list, &sorted] {
+            assert_eq!(list.check_integrity(), Ok(()));
+        }
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            [1, 7, 9, 8, 2, 3, 4, 5, 6]
+        );
+        assert_eq!(
+            sorted.iter().rev().copied().collect::<Vec<_>>(),
+            [20, 10, 5, 0]
+        );
+    }
+
+    #[test]
+    fn split_parts_share_the_key() {
+        let mut list = make_list(&[1, 2, 3, 4]);
+        let back = list.split_off(2);
+        assert_eq!(list.key, back.key);
+        assert!(list.split_into(2).iter().all(|part| part.key == back.key));
+    }
+
+    #[test]
+    fn cached_nodes_are_keyed() {
+        let mut list = make_list(&[1, 2, 3]);
+        list.set_node_cache_limit(3);
+        list.clear();
+        // the last node of the cache links to nothing, which is still mixed with the key
+        let mut last = list.cache.unwrap();
+        for _ in 1..list.cached_nodes() {
+            last =
+                NonNull::new(list.key.decode::<i32>(unsafe { (*last.as_ptr()).xored }) as *mut _)
+                    .unwrap();
+        }
+        unsafe { assert_eq!((*last.as_ptr()).xored, list.key.bits()) };
+        assert_ne!(list.key.bits(), 0);
+
+        list.extend([4, 5, 6]);
+        assert_eq!(list.cached_nodes(), 0);
+        assert_eq!(list.check_integrity(), Ok(()));
+    }
+}
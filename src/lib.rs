@@ -1,23 +1,64 @@
-use std::cmp::Ordering;
-use std::iter::FusedIterator;
-use std::marker::PhantomData;
-use std::mem;
-use std::ptr::NonNull;
+#![no_std]
+
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+#[cfg_attr(test, macro_use)]
+extern crate std;
+
+use alloc::alloc::handle_alloc_error;
+use alloc::collections::{self, VecDeque};
+use alloc::vec::Vec;
+use core::alloc::Layout;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Index, IndexMut};
+use core::ptr::NonNull;
+
+mod allocator;
+mod arena;
+mod cursor;
+mod drain;
+mod error;
+mod handle;
+mod integrity;
+#[cfg(feature = "std")]
+mod io;
+mod key;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod sort;
+#[cfg(test)]
+mod test_util;
+
+pub use allocator::{Global, NodeAllocator};
+pub use arena::{
+    ArenaXorList, IntoIter as ArenaIntoIter, Iter as ArenaIter, IterMut as ArenaIterMut,
+};
+pub use cursor::{Cursor, CursorMut};
+pub use drain::Drain;
+pub use error::{
+    AllocError, IndexOutOfBounds, InsertError, IntegrityError, ListEnd, TryReserveError,
+};
+pub use handle::NodeHandle;
+
+use key::LinkKey;
 
 #[derive(Debug)]
 struct Node<T> {
     value: T,
-    xored: usize, // XOR of prev and next pointers
+    xored: usize, // XOR of prev and next pointers (and the list's key, see `LinkKey`)
 }
 
 impl<T> Node<T> {
-    fn new(value: T) -> Self {
-        Node { value, xored: 0 }
-    }
-
-    #[allow(clippy::boxed_local)]
-    fn into_element(self: Box<Self>) -> T {
-        self.value
+    fn new(value: T, key: LinkKey) -> Self {
+        Node {
+            value,
+            xored: key.encode(0),
+        }
     }
 }
 
@@ -27,11 +68,11 @@ impl<T> Node<T> {
 // ✔ back_mut
 // ✔ clear
 // ✔ contains
-// cursor_back
-// cursor_back_mut
-// cursor_front
-// cursor_front_mut
-// extract_if
+// ✔ cursor_back
+// ✔ cursor_back_mut
+// ✔ cursor_front
+// ✔ cursor_front_mut
+// ✔ extract_if
 // ✔ front
 // ✔ front_mut
 // ✔ is_empty
@@ -45,15 +86,35 @@ impl<T> Node<T> {
 // ✔ push_back_mut
 // ✔ push_front
 // ✔ push_front_mut
-// remove
-// retain
+// ✔ remove
+// ✔ retain
 // ✔ split_off
-#[derive(Debug)]
-pub struct LinkedList<T> {
+/// A doubly linked list whose nodes store a single XOR of their neighbours' addresses.
+///
+/// # Performance
+///
+/// Anything taking an index or a range (`get`, `insert`, `remove`, `split_off`, `drain`,
+/// `rotate_left`, indexing, ...) reaches its node by walking from whichever end of the list
+/// is closer, in O(min(at, len - at)).
+pub struct LinkedList<T, A: NodeAllocator = Global> {
     begin: Option<NonNull<Node<T>>>,
     end: Option<NonNull<Node<T>>>,
 
     len: usize,
+    alloc: A,
+
+    // detached nodes kept for reuse, linked through `xored` as next addresses
+    cache: Option<NonNull<Node<T>>>,
+    cached: usize,
+    max_cached: usize,
+
+    key: LinkKey,
+
+    // tag the handles given out by this list, see `NodeHandle`: `id` is unique among lists
+    // once the first handle was made (0 before), `generation` changes whenever a node is
+    // removed or relinked
+    id: usize,
+    generation: u64,
 }
 
 impl<T> Default for LinkedList<T> {
@@ -64,11 +125,90 @@ impl<T> Default for LinkedList<T> {
 
 impl<T> LinkedList<T> {
     pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    /// Collects `iter` into a new list, like [`FromIterator`], but returns an error
+    /// instead of aborting if a node can't be allocated.
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, TryReserveError> {
+        let mut list = Self::new();
+        for value in iter {
+            list.try_push_back(value)?;
+        }
+        Ok(list)
+    }
+}
+
+impl<T, A: NodeAllocator> LinkedList<T, A> {
+    /// Creates an empty list whose nodes will be allocated by `alloc`.
+    pub fn new_in(alloc: A) -> Self {
         LinkedList {
             begin: None,
             end: None,
             len: 0,
+            alloc,
+            cache: None,
+            cached: 0,
+            max_cached: 0,
+            key: LinkKey::new(),
+            id: 0,
+            generation: 0,
+        }
+    }
+
+    /// Creates an empty list sharing a clone of the allocator and the link key,
+    /// so chains can be moved between the two without re-encoding.
+    pub(crate) fn new_sibling(&self) -> Self
+    where
+        A: Clone,
+    {
+        let mut list = Self::new_in(self.alloc.clone());
+        list.key = self.key;
+        list
+    }
+
+    /// Returns a reference to the allocator of the nodes.
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Sets how many nodes of removed elements the list keeps around for reuse by later
+    /// pushes and inserts, instead of handing them back to the allocator right away.
+    /// The default is 0, which disables the cache. Surplus cached nodes are released.
+    ///
+    /// The limit belongs to this list only, lists created by `split_off`, `clone` etc. start
+    /// without a cache.
+    pub fn set_node_cache_limit(&mut self, max_cached_nodes: usize) {
+        self.max_cached = max_cached_nodes;
+        while self.cached > self.max_cached {
+            self.release_cached_node();
+        }
+    }
+
+    /// Returns the maximum number of nodes kept for reuse, see [`set_node_cache_limit`](Self::set_node_cache_limit).
+    pub fn node_cache_limit(&self) -> usize {
+        self.max_cached
+    }
+
+    /// Returns the number of nodes currently kept for reuse.
+    pub fn cached_nodes(&self) -> usize {
+        self.cached
+    }
+
+    /// Hands all cached nodes back to the allocator. The cache limit stays as it is.
+    pub fn release_cached_nodes(&mut self) {
+        while self.cached > 0 {
+            self.release_cached_node();
+        }
+    }
+
+    fn release_cached_node(&mut self) {
+        let node = self.cache.unwrap();
+        unsafe {
+            self.cache = NonNull::new(self.key.decode::<T>((*node.as_ptr()).xored) as *mut Node<T>);
+            self.alloc.deallocate(node.cast(), Layout::new::<Node<T>>());
         }
+        self.cached -= 1;
     }
 
     pub fn is_empty(&self) -> bool {
@@ -84,14 +224,21 @@ impl<T> LinkedList<T> {
     }
 
     pub fn push_back_mut(&mut self, value: T) -> &mut T {
-        let note_ptr = Box::into_raw(Box::new(Node::new(value)));
-        let mut nnnode = NonNull::new(note_ptr).unwrap();
+        let mut nnnode = self.new_node(value);
         unsafe {
             self.push_back_inner(nnnode);
             &mut nnnode.as_mut().value
         }
     }
 
+    /// Like [`push_back`](Self::push_back), but returns an error instead of aborting
+    /// if the node can't be allocated. `value` is dropped in that case.
+    pub fn try_push_back(&mut self, value: T) -> Result<(), TryReserveError> {
+        let node = self.try_new_node(value)?;
+        unsafe { self.push_back_inner(node) };
+        Ok(())
+    }
+
     #[inline]
     unsafe fn push_back_inner(&mut self, node: NonNull<Node<T>>) {
         unsafe {
@@ -103,13 +250,14 @@ impl<T> LinkedList<T> {
                 }
                 Some(end) => {
                     // could use 'as_mut()' but its just 1 more wrapping call than dereferenced .as_ptr()
-                    (*node.as_ptr()).xored = end.as_ptr() as usize;
+                    (*node.as_ptr()).xored = self.key.encode(end.as_ptr() as usize);
                     (*end.as_ptr()).xored ^= node.as_ptr() as usize;
                     self.end = Some(node);
                 }
             }
         }
         self.len += 1;
+        self.debug_check_integrity();
     }
 
     pub fn push_front(&mut self, value: T) {
@@ -117,14 +265,21 @@ impl<T> LinkedList<T> {
     }
 
     pub fn push_front_mut(&mut self, value: T) -> &mut T {
-        let note_ptr = Box::into_raw(Box::new(Node::new(value)));
-        let mut nnnode = NonNull::new(note_ptr).unwrap();
+        let mut nnnode = self.new_node(value);
         unsafe {
             self.push_front_inner(nnnode);
             &mut nnnode.as_mut().value
         }
     }
 
+    /// Like [`push_front`](Self::push_front), but returns an error instead of aborting
+    /// if the node can't be allocated. `value` is dropped in that case.
+    pub fn try_push_front(&mut self, value: T) -> Result<(), TryReserveError> {
+        let node = self.try_new_node(value)?;
+        unsafe { self.push_front_inner(node) };
+        Ok(())
+    }
+
     #[inline]
     unsafe fn push_front_inner(&mut self, node: NonNull<Node<T>>) {
         unsafe {
@@ -135,25 +290,27 @@ impl<T> LinkedList<T> {
                     self.end = self.begin;
                 }
                 Some(begin) => {
-                    (*node.as_ptr()).xored = begin.as_ptr() as usize;
+                    (*node.as_ptr()).xored = self.key.encode(begin.as_ptr() as usize);
                     (*begin.as_ptr()).xored ^= node.as_ptr() as usize;
                     self.begin = Some(node);
                 }
             }
         }
         self.len += 1;
+        self.debug_check_integrity();
     }
 
     pub fn pop_front(&mut self) -> Option<T> {
-        self.pop_front_node().map(Node::into_element)
+        self.pop_front_node()
+            .map(|node| unsafe { self.free_node(node) })
     }
 
     #[inline]
-    fn pop_front_node(&mut self) -> Option<Box<Node<T>>> {
+    fn pop_front_node(&mut self) -> Option<NonNull<Node<T>>> {
         match self.begin.take() {
             None => None,
             Some(begin) => unsafe {
-                let next = node_next(begin, &mut 0);
+                let next = node_next(begin, &mut 0, self.key);
 
                 if next.is_null() {
                     // there is no next node, this is a single element list
@@ -168,24 +325,27 @@ impl<T> LinkedList<T> {
 
                 // decrease a length
                 self.len -= 1;
+                self.invalidate_handles();
+                self.debug_check_integrity();
 
                 // now - new beginning is set
                 // we can freely work with the original beginning as it is not part of the chain anymore\
-                Some(Box::from_raw(begin.as_ptr()))
+                Some(begin)
             },
         }
     }
 
     pub fn pop_back(&mut self) -> Option<T> {
-        self.pop_back_node().map(Node::into_element)
+        self.pop_back_node()
+            .map(|node| unsafe { self.free_node(node) })
     }
 
     #[inline]
-    fn pop_back_node(&mut self) -> Option<Box<Node<T>>> {
+    fn pop_back_node(&mut self) -> Option<NonNull<Node<T>>> {
         match self.end.take() {
             None => None,
             Some(end) => unsafe {
-                let next = node_next(end, &mut 0);
+                let next = node_next(end, &mut 0, self.key);
 
                 if next.is_null() {
                     // there is no next node, this is a single element list
@@ -199,12 +359,63 @@ impl<T> LinkedList<T> {
                 }
 
                 self.len -= 1;
+                self.invalidate_handles();
+                self.debug_check_integrity();
 
-                Some(Box::from_raw(end.as_ptr()))
+                Some(end)
             },
         }
     }
 
+    /// Allocates a detached node holding `value`, reusing a cached node if there is one.
+    fn new_node(&mut self, value: T) -> NonNull<Node<T>> {
+        match self.try_new_node(value) {
+            Ok(node) => node,
+            Err(err) => handle_alloc_error(err.layout()),
+        }
+    }
+
+    /// Like [`new_node`](Self::new_node), but returns an error (dropping `value`)
+    /// if the allocator fails.
+    fn try_new_node(&mut self, value: T) -> Result<NonNull<Node<T>>, TryReserveError> {
+        let node = match self.cache {
+            Some(node) => {
+                let next = self.key.decode::<T>(unsafe { (*node.as_ptr()).xored });
+                self.cache = NonNull::new(next as *mut Node<T>);
+                self.cached -= 1;
+                node
+            }
+            None => {
+                let layout = Layout::new::<Node<T>>();
+                self.alloc
+                    .allocate(layout)
+                    .map_err(|AllocError| TryReserveError { layout })?
+                    .cast()
+            }
+        };
+        unsafe { node.as_ptr().write(Node::new(value, self.key)) };
+        Ok(node)
+    }
+
+    /// Moves the value out of a detached node and caches the node, or deallocates it
+    /// if the cache is full.
+    ///
+    /// The node must have been allocated by this list's allocator and be unlinked already.
+    unsafe fn free_node(&mut self, node: NonNull<Node<T>>) -> T {
+        unsafe {
+            let Node { value, .. } = node.as_ptr().read();
+            if self.cached < self.max_cached {
+                let next = self.cache.map_or(0, |next| next.as_ptr() as usize);
+                (*node.as_ptr()).xored = self.key.encode(next);
+                self.cache = Some(node);
+                self.cached += 1;
+            } else {
+                self.alloc.deallocate(node.cast(), Layout::new::<Node<T>>());
+            }
+            value
+        }
+    }
+
     pub fn front(&self) -> Option<&T> {
         Self::node_ref(&self.begin)
     }
@@ -221,6 +432,23 @@ impl<T> LinkedList<T> {
         Self::mut_node_ref(&mut self.end)
     }
 
+    /// Returns a reference to the element at the given index, or `None` if it is out of bounds.
+    pub fn get(&self, at: usize) -> Option<&T> {
+        if at >= self.len {
+            return None;
+        }
+        Some(unsafe { &(*self.node_at(at).0.as_ptr()).value })
+    }
+
+    /// Returns a mutable reference to the element at the given index, or `None` if it is
+    /// out of bounds.
+    pub fn get_mut(&mut self, at: usize) -> Option<&mut T> {
+        if at >= self.len {
+            return None;
+        }
+        Some(unsafe { &mut (*self.node_at(at).0.as_ptr()).value })
+    }
+
     fn node_ref(node: &Option<NonNull<Node<T>>>) -> Option<&T> {
         node.as_ref().map(|node| unsafe { &node.as_ref().value })
     }
@@ -230,51 +458,272 @@ impl<T> LinkedList<T> {
             .map(|node| unsafe { &mut node.as_mut().value })
     }
 
+    /// Removes all elements. Their nodes go to the node cache as far as it has room,
+    /// see [`set_node_cache_limit`](Self::set_node_cache_limit).
     pub fn clear(&mut self) {
-        drop(Self {
-            begin: self.begin.take(),
-            end: self.end.take(),
-            len: mem::take(&mut self.len),
-        });
+        while let Some(node) = self.pop_front_node() {
+            drop(unsafe { self.free_node(node) });
+        }
+    }
+
+    /// Reverses the order of the elements in O(1).
+    ///
+    /// Every node stores `prev ^ next`, which reads the same in both directions,
+    /// so swapping `begin` and `end` is all there is to it.
+    pub fn reverse(&mut self) {
+        mem::swap(&mut self.begin, &mut self.end);
+        self.debug_check_integrity();
+    }
+
+    /// Consumes the list and returns it reversed, in O(1). See [`reverse`](Self::reverse).
+    pub fn reversed(mut self) -> Self {
+        self.reverse();
+        self
+    }
+
+    /// Rotates the list `k` places to the left, so the element at index `k` becomes the front
+    /// and the first `k` elements go to the back, in their order.
+    ///
+    /// Only links are rewritten: the two ends are joined into a ring and the ring is cut again
+    /// before index `k`, in O(min(k, len - k)).
+    ///
+    /// Panics if `k > len`.
+    pub fn rotate_left(&mut self, k: usize) {
+        assert!(k <= self.len, "Cannot rotate by more than the length");
+        if k == 0 || k == self.len {
+            return;
+        }
+
+        let (first, last) = self.node_at(k);
+        let (begin, end) = (self.begin.unwrap(), self.end.unwrap());
+        unsafe {
+            // the XOR updates commute, so this holds for two element lists as well
+            (*begin.as_ptr()).xored ^= end.as_ptr() as usize;
+            (*end.as_ptr()).xored ^= begin.as_ptr() as usize;
+            (*first.as_ptr()).xored ^= last;
+            (*(last as *mut Node<T>)).xored ^= first.as_ptr() as usize;
+        }
+        self.begin = Some(first);
+        self.end = NonNull::new(last as *mut Node<T>);
+        self.invalidate_handles();
+        self.debug_check_integrity();
+    }
+
+    /// Rotates the list `k` places to the right, so the last `k` elements go to the front,
+    /// see [`rotate_left`](Self::rotate_left).
+    ///
+    /// Panics if `k > len`.
+    pub fn rotate_right(&mut self, k: usize) {
+        assert!(k <= self.len, "Cannot rotate by more than the length");
+        self.rotate_left(self.len - k);
+    }
+
+    /// Moves the front element to the back in O(1), without touching the allocator.
+    /// The same as `rotate_left(1)`, or a `pop_front` followed by a `push_back`.
+    pub fn move_front_to_back(&mut self) {
+        if self.len > 1 {
+            self.rotate_left(1);
+        }
+    }
+
+    /// Moves the back element to the front in O(1), without touching the allocator.
+    /// The same as `rotate_right(1)`, or a `pop_back` followed by a `push_front`.
+    pub fn move_back_to_front(&mut self) {
+        if self.len > 1 {
+            self.rotate_right(1);
+        }
+    }
+
+    /// Inserts an element at the given index, shifting everything after it towards the back.
+    ///
+    /// Panics if `at > len`.
+    pub fn insert(&mut self, at: usize, value: T) {
+        assert!(at <= self.len, "Cannot insert at a nonexistent index");
+
+        if at == self.len {
+            self.push_back(value);
+        } else {
+            CursorMut::new_at(self, at).insert_before(value);
+        }
+    }
+
+    /// Like [`insert`](Self::insert), but returns an error instead of panicking if `at > len`,
+    /// or instead of aborting if the node can't be allocated. `value` is dropped in both cases.
+    pub fn try_insert(&mut self, at: usize, value: T) -> Result<(), InsertError> {
+        if at > self.len {
+            return Err(IndexOutOfBounds {
+                index: at,
+                len: self.len,
+            }
+            .into());
+        }
+
+        let node = self.try_new_node(value)?;
+        unsafe {
+            if at == self.len {
+                self.push_back_inner(node);
+            } else {
+                CursorMut::new_at(self, at).splice_before_chain(node, node, 1);
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the element at the given index and returns it.
+    ///
+    /// Panics if `at >= len`.
+    pub fn remove(&mut self, at: usize) -> T {
+        assert!(at < self.len, "Cannot remove at a nonexistent index");
+
+        CursorMut::new_at(self, at).remove_current().unwrap()
+    }
+
+    /// Like [`remove`](Self::remove), but returns an error instead of panicking if `at >= len`.
+    pub fn try_remove(&mut self, at: usize) -> Result<T, IndexOutOfBounds> {
+        if at >= self.len {
+            return Err(IndexOutOfBounds {
+                index: at,
+                len: self.len,
+            });
+        }
+        Ok(self.remove(at))
+    }
+
+    /// Finds the node at index `at` (which must be in bounds) together with the address of
+    /// the node before it. Walks from whichever end is closer - thanks to the XOR encoding,
+    /// going backwards is the same operation as going forwards.
+    fn node_at(&self, at: usize) -> (NonNull<Node<T>>, usize) {
+        debug_assert!(at < self.len);
+
+        if at <= self.len / 2 {
+            let mut prev = 0;
+            let mut current = self.begin.unwrap();
+            for _ in 0..at {
+                current = NonNull::new(node_next(current, &mut prev, self.key)).unwrap();
+            }
+            (current, prev)
+        } else {
+            // walking from the end, this holds the node after `current`
+            let mut next = 0;
+            let mut current = self.end.unwrap();
+            for _ in at..self.len - 1 {
+                current = NonNull::new(node_next(current, &mut next, self.key)).unwrap();
+            }
+            let prev = node_link(current, next, self.key);
+            (current, prev)
+        }
     }
 
     /// Splits the list into two at the given index. Returns everything after the given index,
-    /// including the index.
-    pub fn split_off(&mut self, at: usize) -> Self {
+    /// including the index. The returned list shares a clone of the allocator.
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> Self
+    where
+        A: Clone,
+    {
         assert!(at <= self.len, "Cannot split off at a nonexistent index");
 
         if at == 0 {
-            return mem::take(self);
+            return self.take_all();
         } else if at == self.len {
-            return Self::new();
+            return self.new_sibling();
         }
 
-        let mut index = 0;
-        let mut prev_ptr: usize = 0;
-        let mut current = self.begin;
-        while index < at {
-            current = NonNull::new(node_next(current.unwrap(), &mut prev_ptr));
-            index += 1;
+        let (first, prev) = self.node_at(at);
+        let last = self.end.unwrap();
+        let len = self.len - at;
+        let mut list = self.new_sibling();
+        unsafe {
+            self.unlink_chain(prev, 0, first, last, len);
+            list.link_chain(0, 0, first, last, len);
         }
+        list
+    }
 
-        // frst correct the original
-        let at_minus_1 = prev_ptr as *mut Node<T>;
-        unsafe {
-            (*at_minus_1).xored ^= current.unwrap().as_ptr() as usize;
+    /// Splits the list into two at the given index. Returns everything before the given index,
+    /// this list keeps the rest. The counterpart of [`split_off`](Self::split_off).
+    ///
+    /// Panics if `at > len`.
+    pub fn split_front(&mut self, at: usize) -> Self
+    where
+        A: Clone,
+    {
+        assert!(at <= self.len, "Cannot split off at a nonexistent index");
+
+        if at == 0 {
+            return self.new_sibling();
+        } else if at == self.len {
+            return self.take_all();
         }
-        let original_end = mem::replace(&mut self.end, NonNull::new(at_minus_1));
-        let original_len = mem::replace(&mut self.len, at);
 
-        // now establish a new one
-        let mut new_list = Self::new();
+        let (next, last) = self.node_at(at);
+        let first = self.begin.unwrap();
+        let last = NonNull::new(last as *mut Node<T>).unwrap();
+        let mut list = self.new_sibling();
         unsafe {
-            current = current.inspect(|n| (*n.as_ptr()).xored ^= prev_ptr);
+            self.unlink_chain(0, next.as_ptr() as usize, first, last, at);
+            list.link_chain(0, 0, first, last, at);
+        }
+        list
+    }
+
+    /// Consumes the list and splits it into the elements before the given index
+    /// and the rest, see [`split_off`](Self::split_off).
+    ///
+    /// Panics if `at > len`.
+    pub fn split_at(mut self, at: usize) -> (Self, Self)
+    where
+        A: Clone,
+    {
+        let back = self.split_off(at);
+        (self, back)
+    }
+
+    /// Consumes the list and splits it into `n` contiguous lists, in order, whose lengths differ
+    /// by at most one (the longer ones come first). If `n > len`, some of them are empty.
+    /// The list is walked just once and no node is moved.
+    ///
+    /// Panics if `n == 0`.
+    pub fn split_into(mut self, n: usize) -> Vec<Self>
+    where
+        A: Clone,
+    {
+        assert!(n > 0, "Cannot split a list into zero lists");
+
+        let (size, longer) = (self.len / n, self.len % n);
+        let mut lists = Vec::with_capacity(n);
+        let mut prev = 0;
+        let mut current = self.begin;
+        for i in 0..n {
+            let mut list = self.new_sibling();
+            let len = size + usize::from(i < longer);
+            if len > 0 {
+                let first = current.unwrap();
+                let mut last = first;
+                let mut last_prev = prev;
+                for _ in 1..len {
+                    last = NonNull::new(node_next(last, &mut last_prev, self.key)).unwrap();
+                }
+                let next = node_next(last, &mut last_prev, self.key);
+
+                unsafe {
+                    // cut the chain off its neighbours, the next one is decoded already
+                    (*first.as_ptr()).xored ^= prev;
+                    (*last.as_ptr()).xored ^= next as usize;
+                    list.link_chain(0, 0, first, last, len);
+                }
+                prev = last.as_ptr() as usize;
+                current = NonNull::new(next);
+            }
+            lists.push(list);
         }
-        new_list.begin = current;
-        new_list.end = original_end;
-        new_list.len = original_len - at;
 
-        new_list
+        // every node belongs to one of the new lists now
+        self.begin = None;
+        self.end = None;
+        self.len = 0;
+        lists
     }
 
     #[inline]
@@ -283,22 +732,252 @@ impl<T> LinkedList<T> {
             begin: self.begin,
             end: self.end,
             len: self.len,
-            prev: 0,
+            begin_prev: 0,
+            end_next: 0,
+            key: self.key,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a double-ended iterator over the first `n` elements (all of them if `n > len`).
+    pub fn first_n(&self, n: usize) -> Iter<'_, T> {
+        let n = n.min(self.len);
+        if n == 0 {
+            return Iter::default();
+        }
+        let (end, prev) = self.node_at(n - 1);
+        Iter {
+            begin: self.begin,
+            end: Some(end),
+            len: n,
+            begin_prev: 0,
+            end_next: node_link(end, prev, self.key),
+            key: self.key,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a double-ended iterator over the last `n` elements (all of them if `n > len`).
+    pub fn last_n(&self, n: usize) -> Iter<'_, T> {
+        let n = n.min(self.len);
+        if n == 0 {
+            return Iter::default();
+        }
+        let (begin, prev) = self.node_at(self.len - n);
+        Iter {
+            begin: Some(begin),
+            end: self.end,
+            len: n,
+            begin_prev: prev,
+            end_next: 0,
+            key: self.key,
             marker: PhantomData,
         }
     }
 
+    /// Provides an iterator yielding mutable references to the elements.
+    ///
+    /// The list is borrowed mutably for as long as the iterator lives, so it can't be
+    /// aliased by another iterator:
+    ///
+    /// ```compile_fail
+    /// let mut list: xor_ll::LinkedList<i32> = (0..3).collect();
+    /// let mut first = list.iter_mut();
+    /// let mut second = list.iter_mut();
+    /// let a = first.next().unwrap();
+    /// let b = second.next().unwrap();
+    /// *a += *b;
+    /// ```
+    ///
+    /// ```compile_fail
+    /// let mut list: xor_ll::LinkedList<i32> = (0..3).collect();
+    /// let shared = list.iter().next().unwrap();
+    /// let exclusive = list.iter_mut().next().unwrap();
+    /// *exclusive += *shared;
+    /// ```
+    ///
+    /// and it is invariant over `T`, so it can't be used to store a shorter-lived value
+    /// into the list:
+    ///
+    /// ```compile_fail
+    /// use xor_ll::IterMut;
+    ///
+    /// fn shorten<'a, 'b>(iter: IterMut<'a, &'static str>) -> IterMut<'a, &'b str> {
+    ///     iter
+    /// }
+    /// ```
     #[inline]
-    pub fn iter_mut(&self) -> IterMut<'_, T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut {
             begin: self.begin,
             end: self.end,
             len: self.len,
-            prev: 0,
+            begin_prev: 0,
+            end_next: 0,
+            key: self.key,
             marker: PhantomData,
         }
     }
 
+    /// Provides a cursor at the front element, or at the "ghost" non-element if the list is empty.
+    #[inline]
+    pub fn cursor_front(&self) -> Cursor<'_, T, A> {
+        Cursor::new_front(self)
+    }
+
+    /// Provides a cursor at the back element, or at the "ghost" non-element if the list is empty.
+    #[inline]
+    pub fn cursor_back(&self) -> Cursor<'_, T, A> {
+        Cursor::new_back(self)
+    }
+
+    /// Provides a cursor with editing operations at the front element,
+    /// or at the "ghost" non-element if the list is empty.
+    #[inline]
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T, A> {
+        CursorMut::new_front(self)
+    }
+
+    /// Provides a cursor with editing operations at the back element,
+    /// or at the "ghost" non-element if the list is empty.
+    #[inline]
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T, A> {
+        CursorMut::new_back(self)
+    }
+
+    /// Retains only the elements for which the predicate returns `true`,
+    /// unlinking the others in place in a single pass from the front.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|elem| f(elem));
+    }
+
+    /// Like [`retain`](Self::retain), but the predicate gets a mutable reference to each element.
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut cursor = self.cursor_front_mut();
+        while let Some(elem) = cursor.current() {
+            if f(elem) {
+                cursor.move_next();
+            } else {
+                // the cursor keeps the previous node's address and moves on to the next one
+                cursor.remove_current();
+            }
+        }
+    }
+
+    /// Creates an iterator which removes and yields the elements for which `filter` returns `true`.
+    ///
+    /// Elements are visited lazily from the front, each one is unlinked as soon as it's yielded.
+    /// If the iterator is dropped early, or `filter` panics, the list stays valid and keeps
+    /// every element that was not yielded yet.
+    pub fn extract_if<F>(&mut self, filter: F) -> ExtractIf<'_, T, F, A>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ExtractIf {
+            cursor: self.cursor_front_mut(),
+            filter,
+        }
+    }
+
+    /// Removes consecutive repeated elements, keeping the first of each run.
+    /// Returns the number of removed elements.
+    pub fn dedup(&mut self) -> usize
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b)
+    }
+
+    /// Removes consecutive elements that resolve to the same key, see [`dedup`](Self::dedup).
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F) -> usize
+    where
+        K: PartialEq,
+        F: FnMut(&mut T) -> K,
+    {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
+
+    /// Removes consecutive elements satisfying `same_bucket`, see [`dedup`](Self::dedup).
+    ///
+    /// Just like with [`Vec::dedup_by`], `same_bucket(a, b)` gets the element in question as `a`
+    /// and the last kept element before it as `b`, and `a` is removed if it returns `true`.
+    /// The nodes are unlinked during a single pass.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F) -> usize
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let mut removed = 0;
+        let mut cursor = self.cursor_front_mut();
+        let Some(first) = cursor.current() else {
+            return 0;
+        };
+        let mut kept: *mut T = first;
+        cursor.move_next();
+
+        while let Some(elem) = cursor.current() {
+            // `kept` is always a node before the cursor, so the two references don't overlap
+            if same_bucket(elem, unsafe { &mut *kept }) {
+                cursor.remove_current();
+                removed += 1;
+            } else {
+                kept = elem;
+                cursor.move_next();
+            }
+        }
+        removed
+    }
+
+    /// Like [`dedup`](Self::dedup), but instead of dropping the removed elements, returns them
+    /// in their original order. Their nodes are moved over as they are, nothing is allocated.
+    pub fn dedup_into(&mut self) -> Self
+    where
+        T: PartialEq,
+        A: Clone,
+    {
+        let mut removed = self.new_sibling();
+        let mut cursor = self.cursor_front_mut();
+        let Some(first) = cursor.current() else {
+            return removed;
+        };
+        let mut kept: *const T = first;
+        cursor.move_next();
+
+        while let Some(elem) = cursor.current() {
+            if unsafe { *elem == *kept } {
+                // `removed` shares the key, so the node can be linked on as it is
+                let node = cursor.unlink_current().unwrap();
+                let end = removed.end.map_or(0, |end| end.as_ptr() as usize);
+                unsafe { removed.link_chain(end, 0, node, node, 1) };
+            } else {
+                kept = elem;
+                cursor.move_next();
+            }
+        }
+        removed
+    }
+
+    /// Returns the index of the first element matching `predicate`.
+    pub fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().position(predicate)
+    }
+
+    /// Returns the index of the last element matching `predicate`, searching from the back.
+    pub fn rposition<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().rposition(predicate)
+    }
+
     pub fn contains(&self, x: &T) -> bool
     where
         T: PartialEq<T>,
@@ -306,9 +985,38 @@ impl<T> LinkedList<T> {
         self.iter().any(|e| e == x)
     }
 
-    pub fn append(&mut self, other: &mut Self) {
+    /// Moves all elements of `other` to the back of this list, in O(1).
+    ///
+    /// The nodes are taken over as they are and later freed by this list's allocator,
+    /// so both lists have to share it (e.g. one being a clone of the other).
+    ///
+    /// With the `hardened` feature, the links of `other` have to be re-encoded with this list's
+    /// key, which takes O(other.len()), unless `other` came from this list (e.g. `split_off`).
+    ///
+    /// Panics if the allocators of the two lists are not equal.
+    pub fn append(&mut self, other: &mut Self)
+    where
+        A: PartialEq,
+    {
+        assert!(
+            self.alloc == other.alloc,
+            "Cannot append a list using another allocator"
+        );
+        self.append_nodes(other);
+    }
+
+    /// [`append`](Self::append) for lists known to share the allocator.
+    pub(crate) fn append_nodes(&mut self, other: &mut Self) {
+        if let Some(other_begin) = other.begin {
+            unsafe { other.key.rekey_chain(other_begin, self.key) };
+        }
         match self.end {
-            None => mem::swap(self, other),
+            None => {
+                // swap only the chains, each list keeps its own allocator
+                mem::swap(&mut self.begin, &mut other.begin);
+                mem::swap(&mut self.end, &mut other.end);
+                mem::swap(&mut self.len, &mut other.len);
+            }
             Some(end) => {
                 // take the beginning of the given other and connect it with our end
                 if let Some(other_begin) = other.begin.take() {
@@ -321,12 +1029,142 @@ impl<T> LinkedList<T> {
                 }
             }
         }
+        self.invalidate_handles();
+        other.invalidate_handles();
+        self.debug_check_integrity();
+        other.debug_check_integrity();
+    }
+
+    /// Moves all elements into a new list sharing a clone of the allocator.
+    /// Unlike `mem::replace`, the node cache stays with `self`.
+    pub(crate) fn take_all(&mut self) -> Self
+    where
+        A: Clone,
+    {
+        let mut list = self.new_sibling();
+        mem::swap(&mut self.begin, &mut list.begin);
+        mem::swap(&mut self.end, &mut list.end);
+        mem::swap(&mut self.len, &mut list.len);
+        self.invalidate_handles();
+        self.debug_check_integrity();
+        list.debug_check_integrity();
+        list
+    }
+
+    /// Links the detached chain `first..=last` of `len` nodes in between two adjacent nodes
+    /// given by their addresses. Address 0 stands for "no node", so `prev == 0` makes `first`
+    /// the new `begin` and `next == 0` makes `last` the new `end`.
+    ///
+    /// The end nodes of the chain must hold only their inner neighbour in `xored`
+    /// (0 for a single node), exactly as `begin` and `end` of a list do.
+    #[inline]
+    unsafe fn link_chain(
+        &mut self,
+        prev: usize,
+        next: usize,
+        first: NonNull<Node<T>>,
+        last: NonNull<Node<T>>,
+        len: usize,
+    ) {
+        unsafe {
+            // for a single node chain both of these land in the same node, which is what we want
+            (*first.as_ptr()).xored ^= prev;
+            (*last.as_ptr()).xored ^= next;
+
+            match NonNull::new(prev as *mut Node<T>) {
+                None => self.begin = Some(first),
+                Some(prev) => (*prev.as_ptr()).xored ^= next ^ first.as_ptr() as usize,
+            }
+            match NonNull::new(next as *mut Node<T>) {
+                None => self.end = Some(last),
+                Some(next) => (*next.as_ptr()).xored ^= prev ^ last.as_ptr() as usize,
+            }
+        }
+        self.len += len;
+        self.invalidate_handles();
+        self.debug_check_integrity();
+    }
+
+    /// Cuts the chain `first..=last` of `len` nodes out of the list, where `prev` and `next`
+    /// are addresses of the nodes around it (0 if there is none). Afterwards the chain is
+    /// a valid standalone chain, ready to become a list of its own or to be linked elsewhere.
+    #[inline]
+    unsafe fn unlink_chain(
+        &mut self,
+        prev: usize,
+        next: usize,
+        first: NonNull<Node<T>>,
+        last: NonNull<Node<T>>,
+        len: usize,
+    ) {
+        unsafe {
+            match NonNull::new(prev as *mut Node<T>) {
+                None => self.begin = NonNull::new(next as *mut Node<T>),
+                Some(prev) => (*prev.as_ptr()).xored ^= next ^ first.as_ptr() as usize,
+            }
+            match NonNull::new(next as *mut Node<T>) {
+                None => self.end = NonNull::new(prev as *mut Node<T>),
+                Some(next) => (*next.as_ptr()).xored ^= prev ^ last.as_ptr() as usize,
+            }
+
+            (*first.as_ptr()).xored ^= prev;
+            (*last.as_ptr()).xored ^= next;
+        }
+        self.len -= len;
+        self.invalidate_handles();
+        self.debug_check_integrity();
     }
 }
 
-impl<T> Drop for LinkedList<T> {
+impl<T, A: NodeAllocator> Drop for LinkedList<T, A> {
     fn drop(&mut self) {
-        while self.pop_front_node().is_some() {}
+        self.clear();
+        self.release_cached_nodes();
+    }
+}
+
+// The raw node pointers opt us out of the auto traits, but the list owns its nodes
+// exclusively, just like std's LinkedList, so it can go wherever its elements can.
+unsafe impl<T: Send, A: NodeAllocator + Send> Send for LinkedList<T, A> {}
+
+unsafe impl<T: Sync, A: NodeAllocator + Sync> Sync for LinkedList<T, A> {}
+
+/// An iterator produced by [`LinkedList::extract_if`].
+pub struct ExtractIf<'a, T: 'a, F, A: NodeAllocator = Global> {
+    cursor: CursorMut<'a, T, A>,
+    filter: F,
+}
+
+impl<T, F, A: NodeAllocator> Iterator for ExtractIf<'_, T, F, A>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while let Some(elem) = self.cursor.current() {
+            if (self.filter)(elem) {
+                return self.cursor.remove_current();
+            }
+            self.cursor.move_next();
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let cursor = self.cursor.as_cursor();
+        let remaining = cursor
+            .index()
+            .map_or(0, |index| cursor.as_list().len() - index);
+        (0, Some(remaining))
+    }
+}
+
+impl<T: fmt::Debug, F, A: NodeAllocator> fmt::Debug for ExtractIf<'_, T, F, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ExtractIf")
+            .field(&self.cursor.as_cursor().current())
+            .finish()
     }
 }
 
@@ -334,7 +1172,11 @@ pub struct Iter<'a, T> {
     begin: Option<NonNull<Node<T>>>,
     end: Option<NonNull<Node<T>>>,
     len: usize,
-    prev: usize,
+    // the ends are decoded independently, so each keeps its own outer neighbour:
+    // the address of the node before `begin` and of the node after `end`
+    begin_prev: usize,
+    end_next: usize,
+    key: LinkKey,
     marker: PhantomData<&'a Node<T>>,
 }
 
@@ -347,7 +1189,7 @@ impl<'a, T> Iterator for Iter<'a, T> {
         } else {
             self.begin.map(|node| unsafe {
                 let return_ptr = &*node.as_ptr();
-                let next = node_next(node, &mut self.prev);
+                let next = node_next(node, &mut self.begin_prev, self.key);
                 self.begin = NonNull::new(next);
                 self.len -= 1;
                 &return_ptr.value
@@ -373,8 +1215,8 @@ impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
         } else {
             self.end.map(|node| unsafe {
                 let return_ptr = &*node.as_ptr();
-                let next = node_next(node, &mut self.prev);
-                self.end = NonNull::new(next);
+                let prev = node_next(node, &mut self.end_next, self.key);
+                self.end = NonNull::new(prev);
                 self.len -= 1;
                 &return_ptr.value
             })
@@ -384,15 +1226,33 @@ impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
 
 impl<T> ExactSizeIterator for Iter<'_, T> {}
 
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter { ..*self }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
 impl<T> FusedIterator for Iter<'_, T> {}
 
+unsafe impl<T: Sync> Send for Iter<'_, T> {}
+
+unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+
 impl<T> Default for Iter<'_, T> {
     fn default() -> Self {
         Iter {
             begin: None,
             end: None,
             len: 0,
-            prev: 0,
+            begin_prev: 0,
+            end_next: 0,
+            key: LinkKey::default(),
             marker: PhantomData,
         }
     }
@@ -402,8 +1262,12 @@ pub struct IterMut<'a, T: 'a> {
     begin: Option<NonNull<Node<T>>>,
     end: Option<NonNull<Node<T>>>,
     len: usize,
-    prev: usize,
-    marker: PhantomData<&'a Node<T>>,
+    // the ends are decoded independently, so each keeps its own outer neighbour:
+    // the address of the node before `begin` and of the node after `end`
+    begin_prev: usize,
+    end_next: usize,
+    key: LinkKey,
+    marker: PhantomData<&'a mut Node<T>>,
 }
 
 impl<'a, T> Iterator for IterMut<'a, T> {
@@ -416,7 +1280,7 @@ impl<'a, T> Iterator for IterMut<'a, T> {
         } else {
             self.begin.map(|node| unsafe {
                 let return_ptr = &mut *node.as_ptr();
-                let next = node_next(node, &mut self.prev);
+                let next = node_next(node, &mut self.begin_prev, self.key);
                 self.begin = NonNull::new(next);
                 self.len -= 1;
                 &mut return_ptr.value
@@ -443,8 +1307,8 @@ impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
         } else {
             self.end.map(|node| unsafe {
                 let return_ptr = &mut *node.as_ptr();
-                let next = node_next(node, &mut self.prev);
-                self.end = NonNull::new(next);
+                let prev = node_next(node, &mut self.end_next, self.key);
+                self.end = NonNull::new(prev);
                 self.len -= 1;
                 &mut return_ptr.value
             })
@@ -454,54 +1318,135 @@ impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
 
 impl<T> ExactSizeIterator for IterMut<'_, T> {}
 
-impl<T> FusedIterator for IterMut<'_, T> {}
-
-impl<T> Default for IterMut<'_, T> {
-    fn default() -> Self {
-        IterMut {
+impl<T: fmt::Debug> fmt::Debug for IterMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the elements not yielded yet can't be borrowed mutably by anyone else
+        let rest = Iter {
+            begin: self.begin,
+            end: self.end,
+            len: self.len,
+            begin_prev: self.begin_prev,
+            end_next: self.end_next,
+            key: self.key,
+            marker: PhantomData,
+        };
+        f.debug_list().entries(rest).finish()
+    }
+}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+impl<T> Default for IterMut<'_, T> {
+    fn default() -> Self {
+        IterMut {
             begin: None,
             end: None,
             len: 0,
-            prev: 0,
+            begin_prev: 0,
+            end_next: 0,
+            key: LinkKey::default(),
             marker: PhantomData,
         }
     }
 }
 
-impl<T: PartialEq> PartialEq for LinkedList<T> {
+impl<T: PartialEq, A: NodeAllocator> PartialEq for LinkedList<T, A> {
     fn eq(&self, other: &Self) -> bool {
         self.len() == other.len() && self.iter().eq(other)
     }
 }
 
-impl<T: Eq> Eq for LinkedList<T> {}
+impl<T: Eq, A: NodeAllocator> Eq for LinkedList<T, A> {}
 
-impl<T: PartialOrd> PartialOrd for LinkedList<T> {
+impl<T: PartialOrd, A: NodeAllocator> PartialOrd for LinkedList<T, A> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         self.iter().partial_cmp(other)
     }
 }
 
-impl<T: Ord> Ord for LinkedList<T> {
+impl<T: Ord, A: NodeAllocator> Ord for LinkedList<T, A> {
     #[inline]
     fn cmp(&self, other: &Self) -> Ordering {
         self.iter().cmp(other)
     }
 }
 
-fn node_next<T>(node: NonNull<Node<T>>, prev: &mut usize) -> *mut Node<T> {
-    unsafe {
-        let next = ((*node.as_ptr()).xored ^ *prev) as *mut Node<T>;
-        *prev = node.as_ptr() as usize;
-        next
+impl<T: fmt::Debug, A: NodeAllocator> fmt::Debug for LinkedList<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: Hash, A: NodeAllocator> Hash for LinkedList<T, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        for elt in self {
+            elt.hash(state);
+        }
+    }
+}
+
+/// Panics if the index is out of bounds.
+impl<T, A: NodeAllocator> Index<usize> for LinkedList<T, A> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let len = self.len;
+        self.get(index)
+            .unwrap_or_else(|| panic!("{}", IndexOutOfBounds { index, len }))
+    }
+}
+
+impl<T, A: NodeAllocator> IndexMut<usize> for LinkedList<T, A> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len;
+        self.get_mut(index)
+            .unwrap_or_else(|| panic!("{}", IndexOutOfBounds { index, len }))
+    }
+}
+
+impl<T: Clone, A: NodeAllocator + Clone> Clone for LinkedList<T, A> {
+    fn clone(&self) -> Self {
+        let mut list = Self::new_in(self.alloc.clone());
+        list.extend(self.iter().cloned());
+        list
+    }
+
+    /// Reuses the nodes of `self`: surplus ones are dropped, the rest get their values
+    /// overwritten in place and only the missing ones are allocated.
+    fn clone_from(&mut self, source: &Self) {
+        if self.len > source.len {
+            drop(self.split_off(source.len));
+        }
+        for (elem, source_elem) in self.iter_mut().zip(source) {
+            elem.clone_from(source_elem);
+        }
+        let missing = source.iter().skip(self.len).cloned();
+        self.extend(missing);
     }
 }
 
-pub struct IntoIter<T> {
-    list: LinkedList<T>,
+fn node_next<T>(node: NonNull<Node<T>>, prev: &mut usize, key: LinkKey) -> *mut Node<T> {
+    let next = node_link(node, *prev, key) as *mut Node<T>;
+    *prev = node.as_ptr() as usize;
+    next
 }
 
-impl<T> Iterator for IntoIter<T> {
+/// Returns the address of the neighbour of `node` on the other side than `other`.
+#[inline]
+fn node_link<T>(node: NonNull<Node<T>>, other: usize, key: LinkKey) -> usize {
+    key.decode::<T>(unsafe { (*node.as_ptr()).xored } ^ other)
+}
+
+pub struct IntoIter<T, A: NodeAllocator = Global> {
+    list: LinkedList<T, A>,
+}
+
+impl<T, A: NodeAllocator> Iterator for IntoIter<T, A> {
     type Item = T;
 
     #[inline]
@@ -515,16 +1460,22 @@ impl<T> Iterator for IntoIter<T> {
     }
 }
 
-impl<T> DoubleEndedIterator for IntoIter<T> {
+impl<T, A: NodeAllocator> DoubleEndedIterator for IntoIter<T, A> {
     #[inline]
     fn next_back(&mut self) -> Option<T> {
         self.list.pop_back()
     }
 }
 
-impl<T> ExactSizeIterator for IntoIter<T> {}
+impl<T, A: NodeAllocator> ExactSizeIterator for IntoIter<T, A> {}
 
-impl<T> FusedIterator for IntoIter<T> {}
+impl<T: fmt::Debug, A: NodeAllocator> fmt::Debug for IntoIter<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.list).finish()
+    }
+}
+
+impl<T, A: NodeAllocator> FusedIterator for IntoIter<T, A> {}
 
 impl<T> Default for IntoIter<T> {
     fn default() -> Self {
@@ -540,18 +1491,68 @@ impl<T> FromIterator<T> for LinkedList<T> {
     }
 }
 
-impl<T> IntoIterator for LinkedList<T> {
+impl<T, A: NodeAllocator> Extend<T> for LinkedList<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|elt| self.push_back(elt));
+    }
+}
+
+impl<'a, T: 'a + Copy, A: NodeAllocator> Extend<&'a T> for LinkedList<T, A> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for LinkedList<T> {
+    fn from(arr: [T; N]) -> Self {
+        Self::from_iter(arr)
+    }
+}
+
+impl<T> From<Vec<T>> for LinkedList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        Self::from_iter(vec)
+    }
+}
+
+impl<T> From<collections::LinkedList<T>> for LinkedList<T> {
+    fn from(list: collections::LinkedList<T>) -> Self {
+        Self::from_iter(list)
+    }
+}
+
+impl<T> From<LinkedList<T>> for collections::LinkedList<T> {
+    fn from(list: LinkedList<T>) -> Self {
+        Self::from_iter(list)
+    }
+}
+
+impl<T> From<VecDeque<T>> for LinkedList<T> {
+    fn from(deque: VecDeque<T>) -> Self {
+        Self::from_iter(deque)
+    }
+}
+
+impl<T> From<LinkedList<T>> for VecDeque<T> {
+    fn from(list: LinkedList<T>) -> Self {
+        let mut deque = VecDeque::with_capacity(list.len());
+        deque.extend(list);
+        deque
+    }
+}
+
+impl<T, A: NodeAllocator> IntoIterator for LinkedList<T, A> {
     type Item = T;
-    type IntoIter = IntoIter<T>;
+    type IntoIter = IntoIter<T, A>;
 
     /// Consumes the list into an iterator yielding elements by value.
     #[inline]
-    fn into_iter(self) -> IntoIter<T> {
+    fn into_iter(self) -> IntoIter<T, A> {
         IntoIter { list: self }
     }
 }
 
-impl<'a, T> IntoIterator for &'a LinkedList<T> {
+impl<'a, T, A: NodeAllocator> IntoIterator for &'a LinkedList<T, A> {
     type Item = &'a T;
     type IntoIter = Iter<'a, T>;
 
@@ -560,7 +1561,7 @@ impl<'a, T> IntoIterator for &'a LinkedList<T> {
     }
 }
 
-impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+impl<'a, T, A: NodeAllocator> IntoIterator for &'a mut LinkedList<T, A> {
     type Item = &'a mut T;
     type IntoIter = IterMut<'a, T>;
 
@@ -572,18 +1573,12 @@ impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    fn make_list(values: &[i32]) -> LinkedList<i32> {
-        let mut list = LinkedList::new();
-        for &v in values {
-            list.push_back(v);
-        }
-        list
-    }
+    use crate::test_util::{assert_list, make_list};
+    use std::prelude::rust_2024::*;
 
     #[test]
     fn iter_test() {
-        let list = make_list(&(5..=10).into_iter().collect::<Vec<_>>());
+        let list = make_list(&(5..=10).collect::<Vec<_>>());
 
         assert_eq!(
             list.iter().cloned().collect::<Vec<_>>(),
@@ -593,7 +1588,7 @@ mod tests {
 
     #[test]
     fn node_next_test() {
-        let list = make_list(&(5..=10).into_iter().collect::<Vec<_>>());
+        let list = make_list(&(5..=10).collect::<Vec<_>>());
 
         let mut printed = vec![];
 
@@ -602,7 +1597,7 @@ mod tests {
         while let Some(iter) = iter_opt {
             let val = unsafe { (*iter.as_ptr()).value };
             printed.push(val);
-            iter_opt = NonNull::new(node_next(iter, &mut prev));
+            iter_opt = NonNull::new(node_next(iter, &mut prev, list.key));
         }
 
         assert_eq!(printed, (5..=10).rev().collect::<Vec<_>>());
@@ -790,6 +1785,179 @@ mod tests {
         let _ = list.split_off(4);
     }
 
+    #[test]
+    fn split_off_and_split_front_at_every_position() {
+        let values: Vec<i32> = (0..9).collect();
+        for at in 0..=values.len() {
+            let mut list = make_list(&values);
+            let back = list.split_off(at);
+            assert_list(&list, &values[..at]);
+            assert_list(&back, &values[at..]);
+            assert_eq!((list.len(), back.len()), (at, values.len() - at));
+
+            let mut list = make_list(&values);
+            let front = list.split_front(at);
+            assert_list(&front, &values[..at]);
+            assert_list(&list, &values[at..]);
+            assert_eq!((front.len(), list.len()), (at, values.len() - at));
+        }
+    }
+
+    #[test]
+    fn split_at_returns_both_parts() {
+        let (front, back) = make_list(&[0, 1, 2, 3, 4]).split_at(3);
+        assert_list(&front, &[0, 1, 2]);
+        assert_list(&back, &[3, 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_front_panics_on_out_of_bounds() {
+        let mut list = make_list(&[1, 2, 3]);
+        let _ = list.split_front(4);
+    }
+
+    #[test]
+    fn split_into_nearly_equal_parts() {
+        for len in 0..12 {
+            let values: Vec<i32> = (0..len).collect();
+            for n in 1..15 {
+                let parts = make_list(&values).split_into(n);
+                assert_eq!(parts.len(), n);
+
+                let lens: Vec<usize> = parts.iter().map(LinkedList::len).collect();
+                assert!(lens.windows(2).all(|w| w[0] >= w[1] && w[0] - w[1] <= 1));
+
+                let mut joined = Vec::new();
+                for part in &parts {
+                    let forward: Vec<i32> = part.iter().copied().collect();
+                    assert_list(part, &forward);
+                    joined.extend(forward);
+                }
+                assert_eq!(joined, values);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_into_zero_parts_panics() {
+        let _ = make_list(&[1, 2, 3]).split_into(0);
+    }
+
+    #[test]
+    fn rotate_matches_vec_deque() {
+        for len in 0..8 {
+            let values: Vec<i32> = (0..len).collect();
+            for k in 0..=values.len() {
+                let mut list = make_list(&values);
+                let mut expected = VecDeque::from(values.clone());
+                list.rotate_left(k);
+                expected.rotate_left(k);
+                let expected = Vec::from(expected);
+                assert_list(&list, &expected);
+
+                let mut list = make_list(&values);
+                let mut expected = VecDeque::from(values.clone());
+                list.rotate_right(k);
+                expected.rotate_right(k);
+                let expected = Vec::from(expected);
+                assert_list(&list, &expected);
+            }
+        }
+    }
+
+    #[test]
+    fn rotate_keeps_the_nodes() {
+        let mut list = make_list(&[1, 2, 3, 4, 5]);
+        let front = list.front().unwrap() as *const i32;
+        list.rotate_left(2);
+        list.rotate_right(4);
+        list.rotate_left(2);
+        assert_eq!(list.front().unwrap() as *const i32, front);
+        assert_list(&list, &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn move_front_to_back_and_back_to_front() {
+        let mut list = make_list(&[1, 2, 3]);
+        list.move_front_to_back();
+        assert_list(&list, &[2, 3, 1]);
+        list.move_back_to_front();
+        list.move_back_to_front();
+        assert_list(&list, &[3, 1, 2]);
+        assert_eq!(list.back(), Some(&2));
+
+        let mut list = make_list(&[1]);
+        list.move_front_to_back();
+        list.move_back_to_front();
+        assert_list(&list, &[1]);
+        make_list(&[]).move_front_to_back();
+    }
+
+    #[test]
+    #[should_panic]
+    fn rotate_panics_past_the_length() {
+        make_list(&[1, 2, 3]).rotate_left(4);
+    }
+
+    #[test]
+    fn get_and_index_from_both_ends() {
+        let mut list = make_list(&[0, 10, 20, 30, 40, 50, 60]);
+        for at in 0..7 {
+            assert_eq!(list.get(at), Some(&(at as i32 * 10)));
+            assert_eq!(list[at], at as i32 * 10);
+        }
+        assert_eq!(list.get(7), None);
+        assert_eq!(list.get_mut(7), None);
+
+        *list.get_mut(1).unwrap() += 1;
+        list[5] += 5;
+        assert_list(&list, &[0, 11, 20, 30, 40, 55, 60]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 is out of bounds for a list of length 3")]
+    fn index_panics_out_of_bounds() {
+        let list = make_list(&[1, 2, 3]);
+        let _ = list[3];
+    }
+
+    #[test]
+    fn first_n_and_last_n() {
+        let list = make_list(&[0, 1, 2, 3, 4, 5, 6]);
+        for n in 0..=9 {
+            let k = n.min(7);
+            let first: Vec<i32> = list.first_n(n).cloned().collect();
+            assert_eq!(first, (0..k as i32).collect::<Vec<_>>());
+            let first_rev: Vec<i32> = list.first_n(n).rev().cloned().collect();
+            assert_eq!(first_rev, (0..k as i32).rev().collect::<Vec<_>>());
+            assert_eq!(list.first_n(n).len(), k);
+
+            let last: Vec<i32> = list.last_n(n).cloned().collect();
+            assert_eq!(last, (7 - k as i32..7).collect::<Vec<_>>());
+            let last_rev: Vec<i32> = list.last_n(n).rev().cloned().collect();
+            assert_eq!(last_rev, (7 - k as i32..7).rev().collect::<Vec<_>>());
+        }
+
+        let mut iter = list.first_n(4);
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&2));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn position_and_rposition() {
+        let list = make_list(&[1, 2, 3, 2, 1]);
+        assert_eq!(list.position(|&x| x == 2), Some(1));
+        assert_eq!(list.rposition(|&x| x == 2), Some(3));
+        assert_eq!(list.rposition(|&x| x == 1), Some(4));
+        assert_eq!(list.position(|&x| x == 4), None);
+        assert_eq!(list.rposition(|&x| x == 4), None);
+    }
+
     #[test]
     fn mixed_push_pop_sequences() {
         let mut list = LinkedList::new();
@@ -822,7 +1990,7 @@ mod tests {
 
     #[test]
     fn iter_mut_allows_mutation() {
-        let list = make_list(&[1, 2, 3]);
+        let mut list = make_list(&[1, 2, 3]);
         for v in list.iter_mut() {
             *v *= 2;
         }
@@ -866,7 +2034,7 @@ mod tests {
 
     #[test]
     fn iter_mut_next_back_mutates_from_back() {
-        let list = make_list(&[1, 2, 3]);
+        let mut list = make_list(&[1, 2, 3]);
         let mut iter = list.iter_mut();
         assert_eq!(iter.next_back(), Some(&mut 3));
         assert_eq!(iter.next_back(), Some(&mut 2));
@@ -906,7 +2074,7 @@ mod tests {
 
     #[test]
     fn iter_mut_size_hint_and_exact_size() {
-        let list = make_list(&[1, 2, 3]);
+        let mut list = make_list(&[1, 2, 3]);
         let mut iter = list.iter_mut();
         assert_eq!(iter.size_hint(), (3, Some(3)));
         iter.next();
@@ -922,4 +2090,526 @@ mod tests {
         iter.next();
         assert_eq!(iter.size_hint(), (2, Some(2)));
     }
+
+    #[test]
+    fn insert_at_every_position() {
+        for at in 0..=4 {
+            let mut list = make_list(&[0, 1, 2, 3]);
+            list.insert(at, 10);
+
+            let mut expected = vec![0, 1, 2, 3];
+            expected.insert(at, 10);
+            assert_eq!(list.len(), 5);
+            assert_eq!(list.iter().cloned().collect::<Vec<_>>(), expected);
+            expected.reverse();
+            assert_eq!(list.iter().rev().cloned().collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn insert_into_empty_list() {
+        let mut list = LinkedList::new();
+        list.insert(0, 1);
+        assert_eq!(list.front(), Some(&1));
+        assert_eq!(list.back(), Some(&1));
+        assert_eq!(list.len(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_panics_on_out_of_bounds() {
+        let mut list = make_list(&[1, 2, 3]);
+        list.insert(4, 0);
+    }
+
+    #[test]
+    fn remove_at_every_position() {
+        for at in 0..5 {
+            let mut list = make_list(&[0, 1, 2, 3, 4]);
+            assert_eq!(list.remove(at), at as i32);
+
+            let mut expected = vec![0, 1, 2, 3, 4];
+            expected.remove(at);
+            assert_eq!(list.len(), 4);
+            assert_eq!(list.iter().cloned().collect::<Vec<_>>(), expected);
+            expected.reverse();
+            assert_eq!(list.iter().rev().cloned().collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn remove_last_remaining_element() {
+        let mut list = make_list(&[1]);
+        assert_eq!(list.remove(0), 1);
+        assert!(list.is_empty());
+        assert!(list.front().is_none());
+        assert!(list.back().is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_panics_on_out_of_bounds() {
+        let mut list = make_list(&[1, 2, 3]);
+        list.remove(3);
+    }
+
+    #[test]
+    fn try_insert_and_try_remove_report_out_of_bounds() {
+        let mut list = make_list(&[1, 2, 3]);
+
+        assert_eq!(
+            list.try_insert(4, 0),
+            Err(InsertError::OutOfBounds(IndexOutOfBounds {
+                index: 4,
+                len: 3
+            }))
+        );
+        assert_eq!(
+            list.try_remove(3),
+            Err(IndexOutOfBounds { index: 3, len: 3 })
+        );
+        assert_eq!(list.len(), 3);
+
+        assert_eq!(list.try_insert(3, 4), Ok(()));
+        assert_eq!(list.try_remove(0), Ok(1));
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn try_push_and_try_from_iter() {
+        let mut list = LinkedList::try_from_iter([2, 4]).unwrap();
+        assert_eq!(list.try_push_back(5), Ok(()));
+        assert_eq!(list.try_push_front(1), Ok(()));
+        assert_eq!(list.try_insert(2, 3), Ok(()));
+        assert_eq!(
+            list.iter().cloned().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(list.iter().rev().count(), 5);
+    }
+
+    #[test]
+    fn node_at_walks_from_both_ends() {
+        let list = make_list(&(0..9).collect::<Vec<_>>());
+        for at in 0..9 {
+            let (node, prev) = list.node_at(at);
+            assert_eq!(unsafe { (*node.as_ptr()).value }, at as i32);
+            let expected_prev = match at {
+                0 => 0,
+                _ => list.node_at(at - 1).0.as_ptr() as usize,
+            };
+            assert_eq!(prev, expected_prev);
+        }
+    }
+
+    #[test]
+    fn retain_keeps_matching_elements() {
+        let mut list = make_list(&[1, 2, 3, 4, 5, 6]);
+        list.retain(|&x| x % 2 == 0);
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![2, 4, 6]);
+        assert_eq!(
+            list.iter().rev().cloned().collect::<Vec<_>>(),
+            vec![6, 4, 2]
+        );
+    }
+
+    #[test]
+    fn retain_consecutive_removals_and_ends() {
+        let mut list = make_list(&[1, 1, 2, 3, 3, 3, 4, 5, 5]);
+        list.retain(|&x| x == 2 || x == 4);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![2, 4]);
+        assert_eq!(list.front(), Some(&2));
+        assert_eq!(list.back(), Some(&4));
+
+        list.retain(|_| false);
+        assert!(list.is_empty());
+        assert!(list.back().is_none());
+    }
+
+    #[test]
+    fn retain_mut_can_modify_kept_elements() {
+        let mut list = make_list(&[1, 2, 3, 4]);
+        list.retain_mut(|x| {
+            *x *= 10;
+            *x > 15
+        });
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![20, 30, 40]);
+    }
+
+    #[test]
+    fn extract_if_yields_removed_elements() {
+        let mut list = make_list(&[1, 2, 3, 4, 5, 6]);
+        let extracted: Vec<_> = list.extract_if(|x| *x % 3 != 0).collect();
+
+        assert_eq!(extracted, vec![1, 2, 4, 5]);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(list.iter().rev().cloned().collect::<Vec<_>>(), vec![6, 3]);
+    }
+
+    #[test]
+    fn extract_if_is_lazy_and_keeps_list_valid_when_dropped_early() {
+        let mut list = make_list(&[1, 2, 3, 4, 5, 6]);
+        let mut visited = 0;
+        {
+            let mut iter = list.extract_if(|x| {
+                visited += 1;
+                *x % 2 == 0
+            });
+            assert_eq!(iter.next(), Some(2));
+            assert_eq!(iter.size_hint(), (0, Some(4)));
+        }
+        assert_eq!(visited, 2);
+        assert_eq!(list.len(), 5);
+        assert_eq!(
+            list.iter().cloned().collect::<Vec<_>>(),
+            vec![1, 3, 4, 5, 6]
+        );
+        assert_eq!(
+            list.iter().rev().cloned().collect::<Vec<_>>(),
+            vec![6, 5, 4, 3, 1]
+        );
+    }
+
+    #[test]
+    fn extract_if_keeps_list_valid_when_filter_panics() {
+        let mut list = make_list(&[1, 2, 3, 4, 5]);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.extract_if(|x| {
+                assert_ne!(*x, 4);
+                *x % 2 == 1
+            })
+            .for_each(drop);
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![2, 4, 5]);
+        assert_eq!(
+            list.iter().rev().cloned().collect::<Vec<_>>(),
+            vec![5, 4, 2]
+        );
+    }
+
+    #[test]
+    fn dedup_removes_consecutive_duplicates() {
+        let mut list = make_list(&[1, 1, 2, 3, 3, 3, 1, 4, 4]);
+        assert_eq!(list.dedup(), 4);
+        assert_eq!(list.len(), 5);
+        assert_eq!(
+            list.iter().cloned().collect::<Vec<_>>(),
+            vec![1, 2, 3, 1, 4]
+        );
+        assert_eq!(
+            list.iter().rev().cloned().collect::<Vec<_>>(),
+            vec![4, 1, 3, 2, 1]
+        );
+        assert_eq!(list.dedup(), 0);
+
+        let mut list = make_list(&[7, 7, 7]);
+        assert_eq!(list.dedup(), 2);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![7]);
+        assert_eq!(list.back(), Some(&7));
+
+        assert_eq!(make_list(&[]).dedup(), 0);
+    }
+
+    #[test]
+    fn dedup_by_compares_with_the_last_kept_element() {
+        // like Vec, every element is compared with the last kept one, not its direct neighbour
+        let mut list = make_list(&[10, 11, 12, 13, 20, 25, 30]);
+        assert_eq!(list.dedup_by(|a, b| *a - *b < 3), 2);
+        assert_eq!(
+            list.iter().cloned().collect::<Vec<_>>(),
+            vec![10, 13, 20, 25, 30]
+        );
+
+        let mut list = make_list(&[10, 11, 20, 21, 22, 30]);
+        assert_eq!(list.dedup_by_key(|x| *x / 10), 3);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![10, 20, 30]);
+
+        let mut vec = vec![10, 11, 12, 13, 20, 25, 30];
+        vec.dedup_by(|a, b| *a - *b < 3);
+        assert_eq!(vec, vec![10, 13, 20, 25, 30]);
+    }
+
+    #[test]
+    fn dedup_into_moves_the_removed_nodes() {
+        let mut list = make_list(&[1, 1, 2, 2, 2, 3, 1, 1]);
+        let addresses: Vec<*const i32> = list.iter().map(|x| x as *const i32).collect();
+
+        let removed = list.dedup_into();
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![1, 2, 3, 1]);
+        assert_eq!(
+            removed.iter().cloned().collect::<Vec<_>>(),
+            vec![1, 2, 2, 1]
+        );
+        assert_eq!(
+            removed.iter().rev().cloned().collect::<Vec<_>>(),
+            vec![1, 2, 2, 1]
+        );
+        assert_eq!(
+            removed.iter().map(|x| x as *const i32).collect::<Vec<_>>(),
+            [1, 3, 4, 7].map(|i| addresses[i])
+        );
+    }
+
+    #[test]
+    fn reverse_swaps_order() {
+        let mut list = make_list(&[1, 2, 3, 4]);
+        list.reverse();
+
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.front(), Some(&4));
+        assert_eq!(list.back(), Some(&1));
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+        assert_eq!(
+            list.iter().rev().cloned().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn reverse_keeps_list_usable() {
+        let mut list = make_list(&[1, 2, 3]);
+        list.reverse();
+        list.push_back(0);
+        list.push_front(4);
+
+        assert_eq!(list.pop_front(), Some(4));
+        assert_eq!(list.pop_back(), Some(0));
+        assert_eq!(list.remove(1), 2);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![3, 1]);
+
+        list.reverse();
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![1, 3]);
+    }
+
+    #[test]
+    fn reverse_empty_and_single() {
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        empty.reverse();
+        assert!(empty.is_empty());
+
+        let single = make_list(&[1]).reversed();
+        assert_eq!(single.front(), Some(&1));
+        assert_eq!(single.back(), Some(&1));
+    }
+
+    #[test]
+    fn reversed_consumes_and_reverses() {
+        let list = make_list(&[1, 2, 3]).reversed();
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn iter_is_covariant() {
+        fn shorten<'a, 'b>(iter: Iter<'a, &'static str>) -> Iter<'a, &'b str> {
+            iter
+        }
+
+        let list: LinkedList<&'static str> = ["a", "b"].into_iter().collect();
+        assert_eq!(shorten(list.iter()).count(), 2);
+    }
+
+    /// Runs every interleaving of `next`/`next_back` (one step more than there are elements)
+    /// on lists of up to 7 elements and compares the result with a slice iterator.
+    fn for_every_interleaving(mut check: impl FnMut(&[i32], &[bool])) {
+        for n in 0..=7 {
+            let values: Vec<i32> = (0..n).collect();
+            let steps = n as usize + 1;
+            for mask in 0..(1u32 << steps) {
+                let from_front: Vec<bool> = (0..steps).map(|bit| mask & (1 << bit) == 0).collect();
+                check(&values, &from_front);
+            }
+        }
+    }
+
+    #[test]
+    fn iter_every_interleaving_of_both_ends() {
+        for_every_interleaving(|values, from_front| {
+            let list = make_list(values);
+            let mut iter = list.iter();
+            let mut expected = values.iter();
+
+            for &front in from_front {
+                if front {
+                    assert_eq!(iter.next(), expected.next());
+                } else {
+                    assert_eq!(iter.next_back(), expected.next_back());
+                }
+                assert_eq!(iter.len(), expected.len());
+            }
+        });
+    }
+
+    #[test]
+    fn iter_mut_every_interleaving_of_both_ends() {
+        for_every_interleaving(|values, from_front| {
+            let mut list = make_list(values);
+            let mut expected_values = values.to_vec();
+            let mut iter = list.iter_mut();
+            let mut expected = expected_values.iter_mut();
+
+            for &front in from_front {
+                if front {
+                    assert_eq!(iter.next(), expected.next());
+                } else {
+                    assert_eq!(iter.next_back(), expected.next_back());
+                }
+                assert_eq!(iter.len(), expected.len());
+            }
+        });
+    }
+
+    #[test]
+    fn iter_zip_with_rev_detects_palindromes() {
+        let is_palindrome = |list: &LinkedList<i32>| {
+            let half = list.len() / 2;
+            list.iter()
+                .zip(list.iter().rev())
+                .take(half)
+                .all(|(a, b)| a == b)
+        };
+
+        assert!(is_palindrome(&make_list(&[1, 2, 3, 2, 1])));
+        assert!(!is_palindrome(&make_list(&[1, 2, 3, 1, 1])));
+
+        let list = make_list(&[1, 2, 3, 4, 5]);
+        let mut iter = list.iter();
+        let mut pairs = vec![];
+        while let (Some(a), Some(b)) = (iter.next(), iter.next_back()) {
+            pairs.push((*a, *b));
+        }
+        assert_eq!(pairs, vec![(1, 5), (2, 4)]);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn list_and_iterators_are_send_and_sync() {
+        fn assert_send<T: Send>() {}
+        fn assert_sync<T: Sync>() {}
+
+        assert_send::<LinkedList<i32>>();
+        assert_sync::<LinkedList<i32>>();
+        assert_send::<Iter<'_, i32>>();
+        assert_sync::<Iter<'_, i32>>();
+        assert_send::<IterMut<'_, i32>>();
+        assert_sync::<IterMut<'_, i32>>();
+        assert_send::<IntoIter<i32>>();
+        assert_sync::<IntoIter<i32>>();
+        assert_send::<Cursor<'_, i32>>();
+        assert_sync::<Cursor<'_, i32>>();
+        assert_send::<CursorMut<'_, i32>>();
+        assert_sync::<CursorMut<'_, i32>>();
+    }
+
+    #[test]
+    fn debug_prints_elements() {
+        let list = make_list(&[1, 2, 3]);
+        assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+        assert_eq!(format!("{:?}", LinkedList::<i32>::new()), "[]");
+        let mut iter = list.iter();
+        iter.next_back();
+        assert_eq!(format!("{:?}", iter), "[1, 2]");
+
+        let mut list = list;
+        let mut iter = list.iter_mut();
+        iter.next();
+        assert_eq!(format!("{:?}", iter), "[2, 3]");
+    }
+
+    #[test]
+    fn clone_is_deep_and_independent() {
+        let mut list = make_list(&[1, 2, 3]);
+        let cloned = list.clone();
+        list.push_back(4);
+        *list.front_mut().unwrap() = 10;
+
+        assert_eq!(cloned.iter().cloned().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(
+            cloned.iter().rev().cloned().collect::<Vec<_>>(),
+            vec![3, 2, 1]
+        );
+    }
+
+    #[test]
+    fn clone_from_reuses_existing_nodes() {
+        let source = make_list(&[10, 20, 30]);
+
+        let mut longer = make_list(&[1, 2, 3, 4, 5]);
+        let front = longer.front().unwrap() as *const i32;
+        longer.clone_from(&source);
+        assert_eq!(longer, source);
+        assert_eq!(longer.back(), Some(&30));
+        assert_eq!(longer.front().unwrap() as *const i32, front);
+
+        let mut shorter = make_list(&[1]);
+        let front = shorter.front().unwrap() as *const i32;
+        shorter.clone_from(&source);
+        assert_eq!(shorter, source);
+        assert_eq!(
+            shorter.iter().rev().cloned().collect::<Vec<_>>(),
+            vec![30, 20, 10]
+        );
+        assert_eq!(shorter.front().unwrap() as *const i32, front);
+
+        let mut empty = LinkedList::new();
+        empty.clone_from(&source);
+        assert_eq!(empty, source);
+        empty.clone_from(&LinkedList::new());
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn hash_matches_for_equal_lists() {
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash_of<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let a = make_list(&[1, 2, 3]);
+        let b: LinkedList<i32> = LinkedList::from([1, 2, 3]);
+        assert_eq!(hash_of(&a), hash_of(&b));
+        assert_ne!(hash_of(&a), hash_of(&make_list(&[1, 2])));
+
+        // the length prefix keeps nested lists from colliding
+        let split: LinkedList<LinkedList<i32>> = [make_list(&[1]), make_list(&[2, 3])].into();
+        let other: LinkedList<LinkedList<i32>> = [make_list(&[1, 2]), make_list(&[3])].into();
+        assert_ne!(hash_of(&split), hash_of(&other));
+    }
+
+    #[test]
+    fn extend_by_value_and_by_reference() {
+        let mut list = make_list(&[1]);
+        list.extend(vec![2, 3]);
+        list.extend(&[4, 5]);
+
+        assert_eq!(list.len(), 5);
+        assert_eq!(
+            list.iter().cloned().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn conversions_from_and_to_std_collections() {
+        let from_array = LinkedList::from([1, 2, 3]);
+        let from_vec = LinkedList::from(vec![1, 2, 3]);
+        let from_std = LinkedList::from(collections::LinkedList::from([1, 2, 3]));
+        let from_deque = LinkedList::from(VecDeque::from([1, 2, 3]));
+        assert_eq!(from_array, make_list(&[1, 2, 3]));
+        assert_eq!(from_vec, from_array);
+        assert_eq!(from_std, from_array);
+        assert_eq!(from_deque, from_array);
+
+        let std_list: collections::LinkedList<i32> = from_std.into();
+        assert_eq!(std_list, collections::LinkedList::from([1, 2, 3]));
+        let deque: VecDeque<i32> = from_deque.into();
+        assert_eq!(deque, VecDeque::from([1, 2, 3]));
+    }
 }
@@ -0,0 +1,106 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+use crate::{LinkedList, NodeAllocator};
+
+/// Serialized as a sequence, with `len()` as its length.
+impl<T: Serialize, A: NodeAllocator> Serialize for LinkedList<T, A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for elem in self {
+            seq.serialize_element(elem)?;
+        }
+        seq.end()
+    }
+}
+
+/// Deserialized from a sequence, pushing every element to the back as soon as it is read,
+/// without collecting them anywhere first.
+impl<'de, T, A> Deserialize<'de> for LinkedList<T, A>
+where
+    T: Deserialize<'de>,
+    A: NodeAllocator + Default,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(ListVisitor(PhantomData))
+    }
+}
+
+struct ListVisitor<T, A: NodeAllocator>(PhantomData<LinkedList<T, A>>);
+
+impl<'de, T, A> Visitor<'de> for ListVisitor<T, A>
+where
+    T: Deserialize<'de>,
+    A: NodeAllocator + Default,
+{
+    type Value = LinkedList<T, A>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a sequence")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let mut list = LinkedList::new_in(A::default());
+        while let Some(elem) = seq.next_element()? {
+            list.push_back(elem);
+        }
+        Ok(list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::LinkedList;
+    use std::prelude::rust_2024::*;
+
+    #[test]
+    fn json_round_trip() {
+        let list: LinkedList<i32> = (1..=5).collect();
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(json, "[1,2,3,4,5]");
+
+        let back: LinkedList<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, list);
+        assert_eq!(back.iter().rev().count(), 5);
+
+        let empty: LinkedList<String> = serde_json::from_str("[]").unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn nested_json_round_trip() {
+        let list: LinkedList<LinkedList<String>> = [
+            ["a".to_string(), "b".to_string()].into(),
+            LinkedList::new(),
+            ["c".to_string()].into(),
+        ]
+        .into();
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(json, r#"[["a","b"],[],["c"]]"#);
+        assert_eq!(
+            serde_json::from_str::<LinkedList<LinkedList<String>>>(&json).unwrap(),
+            list
+        );
+    }
+
+    #[test]
+    fn bincode_round_trip() {
+        let list: LinkedList<(u8, String)> =
+            [(1, "one".to_string()), (2, "two".to_string())].into();
+        let bytes = bincode::serialize(&list).unwrap();
+        // bincode writes the length up front, so it has to be known before the elements
+        assert_eq!(&bytes[..8], &2u64.to_le_bytes());
+
+        let back: LinkedList<(u8, String)> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(back, list);
+    }
+
+    #[test]
+    fn invalid_element_is_an_error() {
+        assert!(serde_json::from_str::<LinkedList<u8>>("[1, 2, 300]").is_err());
+        assert!(serde_json::from_str::<LinkedList<u8>>("{}").is_err());
+    }
+}
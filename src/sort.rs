@@ -0,0 +1,537 @@
+use core::cmp::Ordering;
+use core::mem;
+use core::ptr::{self, NonNull};
+
+use crate::{LinkedList, Node, NodeAllocator, node_next};
+
+impl<T, A: NodeAllocator> LinkedList<T, A> {
+    /// Sorts the list in place. The sort is stable and runs in O(n log n).
+    ///
+    /// Only the links are rewritten - no element is moved and nothing is allocated.
+    /// See [`sort_by`](Self::sort_by) for what happens if a comparison panics.
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    /// Sorts the list in place with a key extraction function, see [`sort`](Self::sort).
+    /// The key is computed on every comparison.
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    /// Sorts the list in place with a comparator function, see [`sort`](Self::sort).
+    ///
+    /// This is a bottom-up merge sort. If `compare` panics, the list is left holding
+    /// all of its elements in an unspecified order.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if self.len < 2 {
+            return;
+        }
+        MergeSort::new(self).run(&mut compare);
+    }
+}
+
+impl<T, A: NodeAllocator> LinkedList<T, A> {
+    /// Merges the sorted `other` into this sorted list, leaving `other` empty. Equal elements
+    /// of `other` end up after those of `self`, so merging keeps a stable order.
+    ///
+    /// Runs in O(len + other.len) and moves nodes instead of elements, so just like with
+    /// [`append`](Self::append) both lists have to share the allocator.
+    ///
+    /// Panics if the allocators of the two lists are not equal.
+    pub fn merge(&mut self, other: &mut Self)
+    where
+        T: Ord,
+        A: PartialEq,
+    {
+        self.merge_by(other, T::cmp);
+    }
+
+    /// Merges the sorted `other` into this list with a comparator function,
+    /// see [`merge`](Self::merge).
+    ///
+    /// If `compare` panics, every element is left in one of the two lists.
+    pub fn merge_by<F>(&mut self, other: &mut Self, mut compare: F)
+    where
+        A: PartialEq,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        assert!(
+            self.alloc == other.alloc,
+            "Cannot merge a list using another allocator"
+        );
+        let key = self.key;
+        let mut cursor = self.cursor_front_mut();
+        while let Some(value) = other.front() {
+            match cursor.current() {
+                Some(current) if compare(current, value) != Ordering::Greater => {
+                    cursor.move_next();
+                }
+                Some(_) => {
+                    let node = other.pop_front_node().unwrap();
+                    unsafe {
+                        (*node.as_ptr()).xored = key.encode(0);
+                        cursor.splice_before_chain(node, node, 1);
+                    }
+                }
+                // the rest of `other` is not less than anything in `self`
+                None => break,
+            }
+        }
+        self.append_nodes(other);
+    }
+
+    /// Inserts `value` into this sorted list, after all elements equal to it.
+    ///
+    /// The position is searched from both ends at once - from the front for the first greater
+    /// element and from the back for the last one not greater - so the walk is only as long as
+    /// the distance to the nearer end (times two). Inserting below [`front`](Self::front) or
+    /// not below [`back`](Self::back) is O(1).
+    pub fn insert_sorted(&mut self, value: T)
+    where
+        T: Ord,
+    {
+        self.insert_sorted_by(value, T::cmp);
+    }
+
+    /// Inserts `value` into this sorted list by a key extraction function,
+    /// see [`insert_sorted`](Self::insert_sorted).
+    pub fn insert_sorted_by_key<K, F>(&mut self, value: T, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.insert_sorted_by(value, |a, b| f(a).cmp(&f(b)));
+    }
+
+    /// Inserts `value` into this sorted list with a comparator function,
+    /// see [`insert_sorted`](Self::insert_sorted).
+    pub fn insert_sorted_by<F>(&mut self, value: T, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // the node right after the insertion point as seen from the front ...
+        let (mut front, mut front_prev) = (self.begin, 0);
+        // ... and the node right before it as seen from the back
+        let (mut back, mut back_next) = (self.end, 0);
+
+        let (prev, next) = loop {
+            let Some(node) = front else {
+                break (front_prev, 0);
+            };
+            if compare(&value, unsafe { &(*node.as_ptr()).value }) == Ordering::Less {
+                break (front_prev, node.as_ptr() as usize);
+            }
+            front = NonNull::new(node_next(node, &mut front_prev, self.key));
+
+            let Some(node) = back else {
+                break (0, back_next);
+            };
+            if compare(unsafe { &(*node.as_ptr()).value }, &value) != Ordering::Greater {
+                break (node.as_ptr() as usize, back_next);
+            }
+            back = NonNull::new(node_next(node, &mut back_next, self.key));
+        };
+
+        let node = self.new_node(value);
+        unsafe {
+            self.link_chain(prev, next, node, node, 1);
+        }
+    }
+}
+
+/// State of a running sort.
+///
+/// While sorting, the nodes are singly linked through `xored`, which holds just the address
+/// of the next node, not mixed with the list's key. Every node is always in exactly one of:
+/// the merged output of the current pass, the rest of the left run being merged (`psize` nodes
+/// from `p`), or the chain starting at `q`. Dropping the state concatenates these and rebuilds
+/// the XOR links, so the list is valid again no matter whether the sort finished or `compare`
+/// panicked.
+struct MergeSort<'a, T, A: NodeAllocator> {
+    list: &'a mut LinkedList<T, A>,
+    head: *mut Node<T>,
+    tail: *mut Node<T>,
+    p: *mut Node<T>,
+    psize: usize,
+    q: *mut Node<T>,
+}
+
+impl<'a, T, A: NodeAllocator> MergeSort<'a, T, A> {
+    fn new(list: &'a mut LinkedList<T, A>) -> Self {
+        let begin = list.begin.map_or(ptr::null_mut(), |b| b.as_ptr());
+
+        // turn `prev ^ next` into just `next`
+        let mut prev = 0;
+        let mut current = begin;
+        while !current.is_null() {
+            unsafe {
+                let next = list.key.decode::<T>((*current).xored ^ prev);
+                (*current).xored = next;
+                prev = current as usize;
+                current = next as *mut Node<T>;
+            }
+        }
+
+        MergeSort {
+            list,
+            head: ptr::null_mut(),
+            tail: ptr::null_mut(),
+            p: ptr::null_mut(),
+            psize: 0,
+            q: begin,
+        }
+    }
+
+    fn run<F>(mut self, compare: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut run_len = 1;
+        loop {
+            let mut merges = 0;
+            while !self.q.is_null() {
+                merges += 1;
+
+                // the left run starts at `p`, the right one right after it, at `q`
+                self.p = self.q;
+                while self.psize < run_len && !self.q.is_null() {
+                    self.psize += 1;
+                    self.q = next(self.q);
+                }
+
+                let mut qsize = run_len;
+                while self.psize > 0 || (qsize > 0 && !self.q.is_null()) {
+                    let take_left = if self.psize == 0 {
+                        false
+                    } else if qsize == 0 || self.q.is_null() {
+                        true
+                    } else {
+                        // ties go to the left run, which keeps the sort stable
+                        unsafe { compare(&(*self.p).value, &(*self.q).value) != Ordering::Greater }
+                    };
+
+                    let node = if take_left {
+                        self.psize -= 1;
+                        let node = self.p;
+                        self.p = next(node);
+                        node
+                    } else {
+                        qsize -= 1;
+                        let node = self.q;
+                        self.q = next(node);
+                        node
+                    };
+                    self.push_output(node);
+                }
+            }
+            unsafe { (*self.tail).xored = 0 };
+
+            if merges <= 1 {
+                break;
+            }
+            run_len *= 2;
+            // the output of this pass is the input of the next one
+            self.q = mem::replace(&mut self.head, ptr::null_mut());
+            self.tail = ptr::null_mut();
+        }
+    }
+
+    fn push_output(&mut self, node: *mut Node<T>) {
+        if self.tail.is_null() {
+            self.head = node;
+        } else {
+            unsafe { (*self.tail).xored = node as usize };
+        }
+        self.tail = node;
+    }
+}
+
+impl<T, A: NodeAllocator> Drop for MergeSort<'_, T, A> {
+    fn drop(&mut self) {
+        while self.psize > 0 {
+            self.psize -= 1;
+            let node = self.p;
+            self.p = next(node);
+            self.push_output(node);
+        }
+        if self.tail.is_null() {
+            self.head = self.q;
+        } else {
+            unsafe { (*self.tail).xored = self.q as usize };
+        }
+
+        // turn `next` back into `prev ^ next`
+        let mut prev = 0;
+        let mut current = self.head;
+        while !current.is_null() {
+            unsafe {
+                let next = (*current).xored;
+                (*current).xored = self.list.key.encode(prev ^ next);
+                prev = current as usize;
+                current = next as *mut Node<T>;
+            }
+        }
+
+        self.list.begin = NonNull::new(self.head);
+        self.list.end = NonNull::new(prev as *mut Node<T>);
+        self.list.invalidate_handles();
+        self.list.debug_check_integrity();
+    }
+}
+
+/// Next node of a singly linked node, see [`MergeSort`].
+#[inline]
+fn next<T>(node: *mut Node<T>) -> *mut Node<T> {
+    unsafe { (*node).xored as *mut Node<T> }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+    use std::vec::Vec;
+
+    use crate::LinkedList;
+    use crate::test_util::{assert_list, make_list};
+
+    /// Deterministic pseudo random numbers, good enough to shuffle test input.
+    fn pseudo_random(n: usize, seed: u64) -> Vec<i32> {
+        let mut state = seed;
+        (0..n)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 33) as i32 % 100
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sort_short_lists() {
+        let mut list = make_list(&[]);
+        list.sort();
+        assert_list(&list, &[]);
+
+        let mut list = make_list(&[1]);
+        list.sort();
+        assert_list(&list, &[1]);
+
+        let mut list = make_list(&[2, 1]);
+        list.sort();
+        assert_list(&list, &[1, 2]);
+    }
+
+    #[test]
+    fn sort_matches_slice_sort() {
+        for n in [3, 7, 16, 17, 100, 1000] {
+            let values = pseudo_random(n, n as u64);
+            let mut list = make_list(&values);
+            list.sort();
+
+            let mut expected = values.clone();
+            expected.sort();
+            assert_list(&list, &expected);
+
+            list.sort_by(|a, b| b.cmp(a));
+            expected.reverse();
+            assert_list(&list, &expected);
+        }
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        let values: Vec<(i32, usize)> = pseudo_random(200, 7)
+            .into_iter()
+            .map(|v| v % 10)
+            .zip(0..)
+            .collect();
+        let mut list: LinkedList<_> = values.iter().copied().collect();
+        list.sort_by_key(|&(key, _)| key);
+
+        let mut expected = values.clone();
+        expected.sort_by_key(|&(key, _)| key);
+        assert_list(&list, &expected);
+    }
+
+    #[test]
+    fn sort_keeps_the_nodes() {
+        let mut list = make_list(&pseudo_random(50, 3));
+        let mut before: Vec<*const i32> = list.iter().map(|x| x as *const i32).collect();
+        list.sort();
+        let mut after: Vec<*const i32> = list.iter().map(|x| x as *const i32).collect();
+        before.sort();
+        after.sort();
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn panicking_comparator_leaves_a_permutation() {
+        for panic_at in [0, 1, 5, 30, 200] {
+            let values = pseudo_random(64, panic_at);
+            let mut list = make_list(&values);
+
+            let mut comparisons = 0;
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                list.sort_by(|a, b| {
+                    comparisons += 1;
+                    if comparisons > panic_at {
+                        panic!("comparator panicked");
+                    }
+                    a.cmp(b)
+                });
+            }));
+            assert!(result.is_err());
+
+            let forward: Vec<i32> = list.iter().copied().collect();
+            let mut backward: Vec<i32> = list.iter().rev().copied().collect();
+            backward.reverse();
+            assert_eq!(forward, backward);
+            assert_eq!(list.len(), values.len());
+
+            let mut sorted = forward;
+            sorted.sort();
+            let mut expected = values;
+            expected.sort();
+            assert_eq!(sorted, expected);
+
+            // and the list is still fully usable
+            list.sort();
+            list.push_back(100);
+            assert_eq!(list.back(), Some(&100));
+        }
+    }
+
+    #[test]
+    fn merge_sorted_lists() {
+        let mut list = make_list(&[1, 3, 5, 7]);
+        let mut other = make_list(&[0, 2, 3, 8, 9]);
+        list.merge(&mut other);
+        assert_list(&list, &[0, 1, 2, 3, 3, 5, 7, 8, 9]);
+        assert_list(&other, &[]);
+
+        let mut empty = make_list(&[]);
+        list.merge(&mut empty);
+        assert_eq!(list.len(), 9);
+        empty.merge(&mut list);
+        assert_list(&empty, &[0, 1, 2, 3, 3, 5, 7, 8, 9]);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn merge_matches_sorting_the_concatenation() {
+        for seed in 0..10 {
+            let mut a = pseudo_random(20 + seed as usize, seed);
+            let mut b = pseudo_random(30 - seed as usize, seed + 100);
+            a.sort();
+            b.sort();
+            let mut list = make_list(&a);
+            list.merge(&mut make_list(&b));
+
+            let mut expected = [a, b].concat();
+            expected.sort();
+            assert_list(&list, &expected);
+        }
+    }
+
+    #[test]
+    fn merge_is_stable() {
+        let mut list: LinkedList<_> = [(1, 'a'), (2, 'a'), (2, 'b'), (4, 'a')].into();
+        let mut other: LinkedList<_> = [(2, 'c'), (3, 'a'), (4, 'b')].into();
+        list.merge_by(&mut other, |a, b| a.0.cmp(&b.0));
+        assert_list(
+            &list,
+            &[
+                (1, 'a'),
+                (2, 'a'),
+                (2, 'b'),
+                (2, 'c'),
+                (3, 'a'),
+                (4, 'a'),
+                (4, 'b'),
+            ],
+        );
+    }
+
+    #[test]
+    fn panicking_merge_keeps_every_element() {
+        let mut list = make_list(&[1, 3, 5, 7]);
+        let mut other = make_list(&[2, 4, 6]);
+        let mut comparisons = 0;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            list.merge_by(&mut other, |a, b| {
+                comparisons += 1;
+                if comparisons > 3 {
+                    panic!("comparator panicked");
+                }
+                a.cmp(b)
+            });
+        }));
+        assert!(result.is_err());
+
+        assert_list(&list, &[1, 2, 3, 5, 7]);
+        assert_list(&other, &[4, 6]);
+    }
+
+    #[test]
+    fn insert_sorted_finds_the_upper_bound() {
+        let mut list = make_list(&[2, 4, 4, 6]);
+        list.insert_sorted(1);
+        list.insert_sorted(7);
+        list.insert_sorted(6);
+        list.insert_sorted(3);
+        list.insert_sorted(5);
+        assert_list(&list, &[1, 2, 3, 4, 4, 5, 6, 6, 7]);
+
+        let mut list: LinkedList<_> = [(1, 'a'), (2, 'a'), (2, 'b'), (3, 'a')].into();
+        list.insert_sorted_by_key((2, 'c'), |&(key, _)| key);
+        list.insert_sorted_by_key((0, 'a'), |&(key, _)| key);
+        list.insert_sorted_by_key((3, 'b'), |&(key, _)| key);
+        assert_list(
+            &list,
+            &[
+                (0, 'a'),
+                (1, 'a'),
+                (2, 'a'),
+                (2, 'b'),
+                (2, 'c'),
+                (3, 'a'),
+                (3, 'b'),
+            ],
+        );
+    }
+
+    #[test]
+    fn insert_sorted_builds_a_sorted_list() {
+        let values = pseudo_random(300, 42);
+        let mut list = LinkedList::new();
+        for &value in &values {
+            list.insert_sorted(value);
+        }
+        let mut expected = values;
+        expected.sort();
+        assert_list(&list, &expected);
+    }
+
+    #[test]
+    fn insert_sorted_walks_from_the_nearer_end() {
+        let mut list = make_list(&(0..100).map(|x| x * 2).collect::<Vec<_>>());
+        let mut comparisons = 0;
+        list.insert_sorted_by(193, |a, b| {
+            comparisons += 1;
+            a.cmp(b)
+        });
+        assert!(comparisons <= 2 * 4);
+        assert_eq!(list.iter().nth_back(3), Some(&193));
+    }
+}
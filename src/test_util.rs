@@ -0,0 +1,33 @@
+//! Fixtures shared by the unit tests of all modules.
+
+use core::fmt::Debug;
+use std::prelude::rust_2024::*;
+
+use crate::{ArenaXorList, LinkedList};
+
+pub(crate) fn make_list(values: &[i32]) -> LinkedList<i32> {
+    values.iter().copied().collect()
+}
+
+pub(crate) fn make_arena(values: &[i32]) -> ArenaXorList<i32> {
+    values.iter().copied().collect()
+}
+
+/// Asserts that `list` holds exactly `expected`, walking it from both ends.
+pub(crate) fn assert_list<'a, L, T>(list: &'a L, expected: &[T])
+where
+    &'a L: IntoIterator<Item = &'a T>,
+    <&'a L as IntoIterator>::IntoIter: DoubleEndedIterator + ExactSizeIterator,
+    T: PartialEq + Debug + 'a,
+{
+    let iter = list.into_iter();
+    assert_eq!(iter.len(), expected.len());
+    assert_eq!(
+        iter.collect::<Vec<_>>(),
+        expected.iter().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        list.into_iter().rev().collect::<Vec<_>>(),
+        expected.iter().rev().collect::<Vec<_>>()
+    );
+}
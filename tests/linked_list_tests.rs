@@ -1,5 +1,8 @@
 // Integration tests for xor-ll LinkedList<T>
 
+use std::sync::Arc;
+use std::thread;
+
 use xor_ll::LinkedList;
 
 fn make_list(values: &[i32]) -> LinkedList<i32> {
@@ -210,3 +213,76 @@ fn mixed_push_pop_sequences() {
     assert_eq!(list.pop_back(), Some(2));
     assert!(list.pop_front().is_none());
 }
+
+#[test]
+fn insert_and_remove_in_the_middle() {
+    let mut list = make_list(&[1, 2, 4, 5]);
+    list.insert(2, 3);
+    assert_eq!(
+        list.iter().cloned().collect::<Vec<_>>(),
+        vec![1, 2, 3, 4, 5]
+    );
+
+    assert_eq!(list.remove(3), 4);
+    assert_eq!(list.remove(0), 1);
+    assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![2, 3, 5]);
+    assert_eq!(list.back(), Some(&5));
+    assert!(list.try_remove(3).is_err());
+}
+
+#[test]
+fn list_can_be_moved_into_another_thread() {
+    let list = make_list(&[1, 2, 3]);
+    let handle = thread::spawn(move || {
+        let mut list = list;
+        list.push_back(4);
+        list
+    });
+
+    let list = handle.join().unwrap();
+    assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn list_can_be_shared_between_threads() {
+    let list = Arc::new(make_list(&(1..=100).collect::<Vec<_>>()));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let list = Arc::clone(&list);
+            thread::spawn(move || list.iter().sum::<i32>())
+        })
+        .collect();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 5050);
+    }
+}
+
+#[test]
+fn iterators_can_be_sent_to_scoped_threads() {
+    let mut list = make_list(&[1, 2, 3, 4]);
+
+    thread::scope(|s| {
+        let iter = list.iter();
+        let rev = list.iter().rev();
+        let forward = s.spawn(move || iter.cloned().collect::<Vec<_>>());
+        let backward = s.spawn(move || rev.cloned().collect::<Vec<_>>());
+        assert_eq!(forward.join().unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(backward.join().unwrap(), vec![4, 3, 2, 1]);
+    });
+
+    thread::scope(|s| {
+        let iter = list.iter_mut();
+        s.spawn(move || iter.for_each(|x| *x *= 10));
+    });
+    assert_eq!(
+        list.iter().cloned().collect::<Vec<_>>(),
+        vec![10, 20, 30, 40]
+    );
+
+    let into_iter = list.into_iter();
+    let collected = thread::spawn(move || into_iter.rev().collect::<Vec<_>>())
+        .join()
+        .unwrap();
+    assert_eq!(collected, vec![40, 30, 20, 10]);
+}